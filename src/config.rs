@@ -2,6 +2,74 @@ use anyhow::{Context, Result};
 use dotenv::dotenv;
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::str::FromStr;
+
+/// How trades are actually carried out, from safest to riskiest.
+///
+/// Exists so a misconfigured bot can't end up quietly trading (or quietly *not* trading)
+/// without that being obvious: `Sniper`/`SwapRouter` implementations branch on this instead
+/// of silently substituting a placeholder quote or transaction on error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionMode {
+    /// Routers make no network calls: quotes come from a deterministic placeholder generator
+    /// and swaps always report a fabricated success, for local development with no upstream
+    /// trading services available. The event loop does not branch on this mode, so pool
+    /// monitoring and opportunity detection still call their live endpoints (including
+    /// Jupiter's `/quote`).
+    Mock,
+    /// Real quotes are fetched from each router, and a real swap transaction is built, but
+    /// execution stops before signing - the would-be result is reported without the
+    /// transaction ever being submitted.
+    DryRun,
+    /// Full execution against live services. A quote or swap failure is a hard error;
+    /// never silently substituted with a placeholder.
+    Live,
+}
+
+impl FromStr for ExecutionMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "mock" => Ok(ExecutionMode::Mock),
+            "dry_run" | "dryrun" | "dry-run" => Ok(ExecutionMode::DryRun),
+            "live" => Ok(ExecutionMode::Live),
+            other => anyhow::bail!("invalid EXECUTION_MODE '{}': expected mock, dry_run, or live", other),
+        }
+    }
+}
+
+/// Whether a swap solves for a fixed input amount (Jupiter picks the output) or a fixed
+/// output amount (Jupiter picks the input) - useful for sniping a specific target size
+/// rather than spending a fixed amount of SOL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SwapMode {
+    ExactIn,
+    ExactOut,
+}
+
+impl SwapMode {
+    /// The literal Jupiter's `/quote` endpoint expects for `swapMode`.
+    pub fn as_jupiter_str(&self) -> &'static str {
+        match self {
+            SwapMode::ExactIn => "ExactIn",
+            SwapMode::ExactOut => "ExactOut",
+        }
+    }
+}
+
+impl FromStr for SwapMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace(['_', '-'], "").as_str() {
+            "exactin" => Ok(SwapMode::ExactIn),
+            "exactout" => Ok(SwapMode::ExactOut),
+            other => anyhow::bail!("invalid SWAP_MODE '{}': expected exact_in or exact_out", other),
+        }
+    }
+}
 
 /// Configuration structure for the SAI-HF Bot
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,11 +82,48 @@ pub struct Config {
     pub trade_sol_amount: f64,
     pub slippage_bps: u16,
     pub max_slippage_bps: u16,
-    
+    pub min_profit_threshold: f64,
+    pub max_position_size_sol: f64,
+    pub stale_opportunity_slot_tolerance: u64,
+    pub max_price_deviation_bps: u16,
+
+    // Jupiter Configuration
+    pub jupiter_api_url: String,
+    pub jupiter_timeout_ms: u64,
+
+    // Yellowstone gRPC Configuration
+    pub yellowstone_grpc_url: String,
+    pub yellowstone_grpc_token: Option<String>,
+    pub amm_program_ids: Vec<String>,
+    pub watched_pool_accounts: Vec<String>,
+    /// Token mint pair for each entry in `watched_pool_accounts`, in the same order - the
+    /// gRPC account layout decoded in `decode_pool_account` only carries raw reserve
+    /// amounts, not mint addresses, so the mints have to be plumbed in from config instead.
+    pub watched_pool_mints: Vec<(String, String)>,
+
+    // Swap Router Configuration
+    pub enabled_routers: Vec<String>,
+    pub sanctum_api_url: String,
+    pub execution_mode: ExecutionMode,
+    pub default_swap_mode: SwapMode,
+    /// Assumed worst-case price movement between quote and execution, applied on top of a
+    /// quote's own `other_amount_threshold` when checking profitability.
+    pub slippage_buffer_bps: u16,
+    /// Whether `analyze_pools` corroborates the reserve-ratio price against a fallback
+    /// oracle before quoting. The only fallback source wired up (`PythOracleSource`) is a
+    /// placeholder that's always unavailable, so leaving this on would make
+    /// `corroborate_price` skip every pool forever - defaults to `false` until a real
+    /// second source is wired up, and should be enabled at the same time that happens.
+    pub enable_price_corroboration: bool,
+
     // Bot Configuration
     pub pool_check_interval_ms: u64,
     pub max_retries: u32,
-    
+    pub opportunity_channel_capacity: usize,
+    pub execution_concurrency: usize,
+    pub tx_confirmation_timeout_ms: u64,
+    pub tx_confirmation_poll_ms: u64,
+
     // Wallet Configuration
     pub wallet_private_key: String,
     
@@ -30,6 +135,49 @@ pub struct Config {
     pub log_level: String,
 }
 
+impl Default for Config {
+    /// A baseline config for tests: live-looking URLs, conservative trading parameters,
+    /// and no watched pools/routers beyond Jupiter. Tests build off this with struct-update
+    /// syntax (`Config { field: ..., ..Default::default() }`) and override only the fields
+    /// relevant to what they're exercising, instead of each re-listing every field.
+    fn default() -> Self {
+        Config {
+            solana_rpc_url: "https://api.mainnet-beta.solana.com".to_string(),
+            solana_ws_url: "wss://api.mainnet-beta.solana.com".to_string(),
+            trade_sol_amount: 0.1,
+            slippage_bps: 50,
+            max_slippage_bps: 100,
+            min_profit_threshold: 0.01,
+            max_position_size_sol: 5.0,
+            stale_opportunity_slot_tolerance: 10,
+            max_price_deviation_bps: 200,
+            jupiter_api_url: "https://quote-api.jup.ag/v6".to_string(),
+            jupiter_timeout_ms: 2000,
+            yellowstone_grpc_url: "https://api.mainnet-beta.solana.com:443".to_string(),
+            yellowstone_grpc_token: None,
+            amm_program_ids: Vec::new(),
+            watched_pool_accounts: Vec::new(),
+            watched_pool_mints: Vec::new(),
+            enabled_routers: vec!["jupiter".to_string()],
+            sanctum_api_url: "https://api.sanctum.so/v1".to_string(),
+            execution_mode: ExecutionMode::Mock,
+            default_swap_mode: SwapMode::ExactIn,
+            slippage_buffer_bps: 100,
+            enable_price_corroboration: false,
+            pool_check_interval_ms: 1000,
+            max_retries: 3,
+            opportunity_channel_capacity: 256,
+            execution_concurrency: 4,
+            tx_confirmation_timeout_ms: 30000,
+            tx_confirmation_poll_ms: 500,
+            wallet_private_key: "test_wallet".to_string(),
+            dashboard_port: 8080,
+            enable_dashboard: true,
+            log_level: "info".to_string(),
+        }
+    }
+}
+
 impl Config {
     /// Load configuration from environment variables
     pub fn load() -> Result<Self> {
@@ -56,7 +204,101 @@ impl Config {
                 .unwrap_or_else(|_| "100".to_string())
                 .parse()
                 .context("Failed to parse MAX_SLIPPAGE_BPS")?,
-            
+
+            min_profit_threshold: env::var("MIN_PROFIT_THRESHOLD")
+                .unwrap_or_else(|_| "0.01".to_string())
+                .parse()
+                .context("Failed to parse MIN_PROFIT_THRESHOLD")?,
+
+            max_position_size_sol: env::var("MAX_POSITION_SIZE_SOL")
+                .unwrap_or_else(|_| "5.0".to_string())
+                .parse()
+                .context("Failed to parse MAX_POSITION_SIZE_SOL")?,
+
+            stale_opportunity_slot_tolerance: env::var("STALE_OPPORTUNITY_SLOT_TOLERANCE")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .context("Failed to parse STALE_OPPORTUNITY_SLOT_TOLERANCE")?,
+
+            max_price_deviation_bps: env::var("MAX_PRICE_DEVIATION_BPS")
+                .unwrap_or_else(|_| "200".to_string())
+                .parse()
+                .context("Failed to parse MAX_PRICE_DEVIATION_BPS")?,
+
+            jupiter_api_url: env::var("JUPITER_API_URL")
+                .unwrap_or_else(|_| "https://quote-api.jup.ag/v6".to_string()),
+
+            jupiter_timeout_ms: env::var("JUPITER_TIMEOUT_MS")
+                .unwrap_or_else(|_| "2000".to_string())
+                .parse()
+                .context("Failed to parse JUPITER_TIMEOUT_MS")?,
+
+            yellowstone_grpc_url: env::var("YELLOWSTONE_GRPC_URL")
+                .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com:443".to_string()),
+
+            yellowstone_grpc_token: env::var("YELLOWSTONE_GRPC_TOKEN").ok(),
+
+            amm_program_ids: env::var("AMM_PROGRAM_IDS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+
+            watched_pool_accounts: env::var("WATCHED_POOL_ACCOUNTS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+
+            // "tokenAMint:tokenBMint,tokenAMint2:tokenBMint2", one pair per entry in
+            // WATCHED_POOL_ACCOUNTS, in the same order.
+            watched_pool_mints: env::var("WATCHED_POOL_MINTS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|pair| {
+                    pair.split_once(':')
+                        .map(|(a, b)| (a.trim().to_string(), b.trim().to_string()))
+                        .with_context(|| format!("WATCHED_POOL_MINTS entry '{}' is not 'tokenA:tokenB'", pair))
+                })
+                .collect::<Result<Vec<_>>>()?,
+
+            enabled_routers: env::var("ENABLED_ROUTERS")
+                .unwrap_or_else(|_| "jupiter".to_string())
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+
+            sanctum_api_url: env::var("SANCTUM_API_URL")
+                .unwrap_or_else(|_| "https://api.sanctum.so/v1".to_string()),
+
+            execution_mode: env::var("EXECUTION_MODE")
+                .unwrap_or_else(|_| "live".to_string())
+                .parse()
+                .context("Failed to parse EXECUTION_MODE")?,
+
+            default_swap_mode: env::var("SWAP_MODE")
+                .unwrap_or_else(|_| "exact_in".to_string())
+                .parse()
+                .context("Failed to parse SWAP_MODE")?,
+
+            slippage_buffer_bps: env::var("SLIPPAGE_BUFFER_BPS")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .context("Failed to parse SLIPPAGE_BUFFER_BPS")?,
+
+            enable_price_corroboration: env::var("ENABLE_PRICE_CORROBORATION")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .context("Failed to parse ENABLE_PRICE_CORROBORATION")?,
+
             pool_check_interval_ms: env::var("POOL_CHECK_INTERVAL_MS")
                 .unwrap_or_else(|_| "1000".to_string())
                 .parse()
@@ -66,7 +308,27 @@ impl Config {
                 .unwrap_or_else(|_| "3".to_string())
                 .parse()
                 .context("Failed to parse MAX_RETRIES")?,
-            
+
+            opportunity_channel_capacity: env::var("OPPORTUNITY_CHANNEL_CAPACITY")
+                .unwrap_or_else(|_| "256".to_string())
+                .parse()
+                .context("Failed to parse OPPORTUNITY_CHANNEL_CAPACITY")?,
+
+            execution_concurrency: env::var("EXECUTION_CONCURRENCY")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .context("Failed to parse EXECUTION_CONCURRENCY")?,
+
+            tx_confirmation_timeout_ms: env::var("TX_CONFIRMATION_TIMEOUT_MS")
+                .unwrap_or_else(|_| "30000".to_string())
+                .parse()
+                .context("Failed to parse TX_CONFIRMATION_TIMEOUT_MS")?,
+
+            tx_confirmation_poll_ms: env::var("TX_CONFIRMATION_POLL_MS")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()
+                .context("Failed to parse TX_CONFIRMATION_POLL_MS")?,
+
             wallet_private_key: env::var("WALLET_PRIVATE_KEY")
                 .unwrap_or_else(|_| "your_private_key_here".to_string()),
             
@@ -99,11 +361,43 @@ impl Config {
         if self.slippage_bps > self.max_slippage_bps {
             anyhow::bail!("SLIPPAGE_BPS cannot exceed MAX_SLIPPAGE_BPS");
         }
-        
+
+        if self.max_position_size_sol <= 0.0 {
+            anyhow::bail!("MAX_POSITION_SIZE_SOL must be positive");
+        }
+
         if self.pool_check_interval_ms == 0 {
             anyhow::bail!("POOL_CHECK_INTERVAL_MS must be greater than 0");
         }
-        
+
+        if self.jupiter_timeout_ms == 0 {
+            anyhow::bail!("JUPITER_TIMEOUT_MS must be greater than 0");
+        }
+
+        if self.opportunity_channel_capacity == 0 {
+            anyhow::bail!("OPPORTUNITY_CHANNEL_CAPACITY must be greater than 0");
+        }
+
+        if self.execution_concurrency == 0 {
+            anyhow::bail!("EXECUTION_CONCURRENCY must be greater than 0");
+        }
+
+        if self.tx_confirmation_timeout_ms == 0 {
+            anyhow::bail!("TX_CONFIRMATION_TIMEOUT_MS must be greater than 0");
+        }
+
+        if self.tx_confirmation_poll_ms == 0 {
+            anyhow::bail!("TX_CONFIRMATION_POLL_MS must be greater than 0");
+        }
+
+        if !self.watched_pool_mints.is_empty() && self.watched_pool_mints.len() != self.watched_pool_accounts.len() {
+            anyhow::bail!(
+                "WATCHED_POOL_MINTS must have one entry per WATCHED_POOL_ACCOUNTS entry ({} vs {})",
+                self.watched_pool_mints.len(),
+                self.watched_pool_accounts.len()
+            );
+        }
+
         Ok(())
     }
 }
@@ -111,32 +405,29 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_config_validation() {
-        let mut config = Config {
-            solana_rpc_url: "https://api.mainnet-beta.solana.com".to_string(),
-            solana_ws_url: "wss://api.mainnet-beta.solana.com".to_string(),
-            trade_sol_amount: 0.1,
-            slippage_bps: 50,
-            max_slippage_bps: 100,
-            pool_check_interval_ms: 1000,
-            max_retries: 3,
-            wallet_private_key: "test".to_string(),
-            dashboard_port: 8080,
-            enable_dashboard: true,
-            log_level: "info".to_string(),
-        };
-        
+        let mut config = Config::default();
+
         assert!(config.validate().is_ok());
-        
+
         // Test invalid trade amount
         config.trade_sol_amount = -0.1;
         assert!(config.validate().is_err());
-        
+
         // Reset and test invalid slippage
         config.trade_sol_amount = 0.1;
         config.slippage_bps = 150;
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_config_validation_rejects_mismatched_watched_pool_mints() {
+        let mut config = Config::default();
+        config.watched_pool_accounts = vec!["pool1".to_string(), "pool2".to_string()];
+        config.watched_pool_mints = vec![("SOL".to_string(), "USDC".to_string())];
+
+        assert!(config.validate().is_err());
+    }
 }
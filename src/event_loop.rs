@@ -1,9 +1,21 @@
-use crate::config::Config;
-use anyhow::Result;
+use crate::config::{Config, SwapMode};
+use crate::metrics::Metrics;
+use crate::price_feed::{self, PriceSource};
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use futures::StreamExt;
+use indexmap::IndexSet;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
 use tracing::{error, info, warn};
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, SubscribeRequest, SubscribeRequestFilterAccounts,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolData {
@@ -14,6 +26,8 @@ pub struct PoolData {
     pub liquidity_b: f64,
     pub price: f64,
     pub last_updated: i64,
+    /// Slot at which this pool state was observed, used to detect stale opportunities.
+    pub slot: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +39,10 @@ pub struct TradeOpportunity {
     pub expected_amount_out: f64,
     pub expected_profit: f64,
     pub timestamp: i64,
+    /// Slot of the `PoolData` snapshot this opportunity was derived from.
+    pub slot: u64,
+    /// Whether the eventual swap should solve for a fixed input or a fixed output amount.
+    pub swap_mode: SwapMode,
 }
 
 pub struct EventLoop {
@@ -32,18 +50,44 @@ pub struct EventLoop {
     pools: Arc<RwLock<Vec<PoolData>>>,
     opportunities: Arc<RwLock<Vec<TradeOpportunity>>>,
     http_client: reqwest::Client,
+    metrics: Arc<Metrics>,
+    /// Sending half of the bounded opportunity channel detection pushes into; execution
+    /// workers hold the receiving half, taken once via `take_opportunity_receiver`.
+    opportunity_tx: mpsc::Sender<TradeOpportunity>,
+    opportunity_rx: StdMutex<Option<mpsc::Receiver<TradeOpportunity>>>,
+    /// Pool addresses with an opportunity currently enqueued or executing, so detection
+    /// doesn't enqueue the same pool twice while one is in-flight.
+    in_flight: Arc<RwLock<IndexSet<String>>>,
 }
 
 impl EventLoop {
-    pub fn new(config: Arc<Config>) -> Self {
+    pub fn new(config: Arc<Config>, metrics: Arc<Metrics>) -> Self {
+        let (opportunity_tx, opportunity_rx) = mpsc::channel(config.opportunity_channel_capacity);
+
         Self {
             config,
             pools: Arc::new(RwLock::new(Vec::new())),
             opportunities: Arc::new(RwLock::new(Vec::new())),
             http_client: reqwest::Client::new(),
+            metrics,
+            opportunity_tx,
+            opportunity_rx: StdMutex::new(Some(opportunity_rx)),
+            in_flight: Arc::new(RwLock::new(IndexSet::new())),
         }
     }
 
+    /// Take the receiving half of the opportunity channel. Intended to be called once,
+    /// at startup, by whoever spawns the execution worker pool.
+    pub fn take_opportunity_receiver(&self) -> Option<mpsc::Receiver<TradeOpportunity>> {
+        self.opportunity_rx.lock().expect("opportunity_rx mutex poisoned").take()
+    }
+
+    /// Mark a pool's in-flight opportunity as finished, allowing detection to enqueue a
+    /// fresh opportunity for it again.
+    pub async fn mark_complete(&self, pool_address: &str) {
+        self.in_flight.write().await.shift_remove(pool_address);
+    }
+
     /// Start the main event loop for monitoring liquidity pools
     pub async fn run(&self) -> Result<()> {
         info!("Starting event loop for pool monitoring");
@@ -52,28 +96,34 @@ impl EventLoop {
         let pools = self.pools.clone();
         let opportunities = self.opportunities.clone();
         let http_client = self.http_client.clone();
+        let metrics = self.metrics.clone();
 
         // Spawn tasks for different monitoring functions
         let moralis_task = tokio::spawn(Self::monitor_moralis_pools(
             config.clone(),
             pools.clone(),
             http_client.clone(),
+            metrics.clone(),
         ));
 
-        let helius_task = tokio::spawn(Self::monitor_helius_webhooks(
+        let grpc_task = tokio::spawn(Self::monitor_pool_account_stream(
             config.clone(),
             pools.clone(),
-            http_client.clone(),
+            metrics.clone(),
         ));
 
         let opportunity_task = tokio::spawn(Self::detect_opportunities(
             config.clone(),
             pools.clone(),
             opportunities.clone(),
+            http_client.clone(),
+            metrics.clone(),
+            self.opportunity_tx.clone(),
+            self.in_flight.clone(),
         ));
 
         // Wait for all tasks (they should run indefinitely)
-        let _ = tokio::try_join!(moralis_task, helius_task, opportunity_task)?;
+        let _ = tokio::try_join!(moralis_task, grpc_task, opportunity_task)?;
 
         Ok(())
     }
@@ -83,10 +133,12 @@ impl EventLoop {
         config: Arc<Config>,
         pools: Arc<RwLock<Vec<PoolData>>>,
         client: reqwest::Client,
+        metrics: Arc<Metrics>,
     ) -> Result<()> {
         info!("Starting Moralis pool monitoring");
-        
+
         loop {
+            let started_at = tokio::time::Instant::now();
             match Self::fetch_moralis_pools(&config, &client).await {
                 Ok(fetched_pools) => {
                     let mut pools_lock = pools.write().await;
@@ -97,6 +149,7 @@ impl EventLoop {
                     error!("Error fetching Moralis pools: {}", e);
                 }
             }
+            metrics.record_pool_fetch(started_at.elapsed()).await;
 
             tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
         }
@@ -133,21 +186,225 @@ impl EventLoop {
         }
     }
 
-    /// Monitor Helius webhooks for real-time updates
-    async fn monitor_helius_webhooks(
-        _config: Arc<Config>,
-        _pools: Arc<RwLock<Vec<PoolData>>>,
-        _client: reqwest::Client,
+    /// Stream pool account writes from a Yellowstone/Geyser gRPC endpoint.
+    ///
+    /// Replaces the old webhook placeholder with sub-slot updates: on each (re)connect we
+    /// seed `pools` with a snapshot so the cache isn't cold, then apply incremental
+    /// account-write updates as they arrive. Stream drops are retried with bounded
+    /// exponential backoff rather than crashing the task.
+    async fn monitor_pool_account_stream(
+        config: Arc<Config>,
+        pools: Arc<RwLock<Vec<PoolData>>>,
+        metrics: Arc<Metrics>,
     ) -> Result<()> {
-        info!("Starting Helius webhook monitoring");
-        
+        info!("Starting Yellowstone gRPC pool account stream");
+
+        let mut backoff = Duration::from_secs(1);
+        const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
         loop {
-            // Placeholder for Helius webhook integration
-            // In production, this would listen to webhooks or poll Helius API
-            warn!("Helius webhook integration is a placeholder");
-            
-            tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+            match Self::run_yellowstone_stream(&config, &pools, &metrics).await {
+                Ok(()) => {
+                    warn!("Yellowstone gRPC stream ended; reconnecting");
+                    backoff = Duration::from_secs(1);
+                }
+                Err(e) => {
+                    error!(
+                        "Yellowstone gRPC stream error: {} (retrying in {:?})",
+                        e, backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    }
+
+    /// Connect to Yellowstone, seed a snapshot of the watched pool accounts, then stream
+    /// incremental account-write updates until the stream closes or errors.
+    async fn run_yellowstone_stream(
+        config: &Config,
+        pools: &Arc<RwLock<Vec<PoolData>>>,
+        metrics: &Metrics,
+    ) -> Result<()> {
+        let mut client =
+            GeyserGrpcClient::build_from_shared(config.yellowstone_grpc_url.clone())
+                .context("invalid Yellowstone gRPC endpoint")?
+                .x_token(config.yellowstone_grpc_token.clone())
+                .context("invalid Yellowstone gRPC token")?
+                .connect()
+                .await
+                .context("failed to connect to Yellowstone gRPC endpoint")?;
+
+        Self::seed_pool_snapshot(config, pools).await?;
+
+        let mut accounts_filter = HashMap::new();
+        accounts_filter.insert(
+            "sai_hf_bot_pools".to_string(),
+            SubscribeRequestFilterAccounts {
+                account: config.watched_pool_accounts.clone(),
+                owner: config.amm_program_ids.clone(),
+                ..Default::default()
+            },
+        );
+
+        let request = SubscribeRequest {
+            accounts: accounts_filter,
+            ..Default::default()
+        };
+
+        let (_subscribe_tx, mut stream) = client
+            .subscribe_with_request(Some(request))
+            .await
+            .context("failed to open Yellowstone account subscription")?;
+
+        let mints = Self::watched_pool_mints(config);
+
+        while let Some(update) = stream.next().await {
+            let received_at = tokio::time::Instant::now();
+            let update = update.context("Yellowstone stream yielded an error")?;
+
+            let Some(UpdateOneof::Account(account_update)) = update.update_oneof else {
+                continue;
+            };
+            let Some(account) = account_update.account else {
+                continue;
+            };
+
+            let pool_address = bs58::encode(&account.pubkey).into_string();
+            match Self::decode_pool_account(pool_address.clone(), &account.data, account_update.slot, &mints) {
+                Ok(pool) => {
+                    let mut pools_lock = pools.write().await;
+                    match pools_lock.iter_mut().find(|p| p.pool_address == pool.pool_address) {
+                        Some(existing) => *existing = pool,
+                        None => pools_lock.push(pool),
+                    }
+                    drop(pools_lock);
+                    metrics.record_pool_fetch(received_at.elapsed()).await;
+                }
+                Err(e) => warn!("Failed to decode pool account update for {}: {}", pool_address, e),
+            }
         }
+
+        Ok(())
+    }
+
+    /// Seed `pools` with a `getMultipleAccounts` snapshot of the watched pool accounts so
+    /// the cache isn't cold while the gRPC stream starts delivering incremental writes.
+    async fn seed_pool_snapshot(config: &Config, pools: &Arc<RwLock<Vec<PoolData>>>) -> Result<()> {
+        if config.watched_pool_accounts.is_empty() {
+            return Ok(());
+        }
+
+        let client = reqwest::Client::new();
+        let current_slot = Self::fetch_current_slot(config, &client).await.unwrap_or(0);
+
+        let response = client
+            .post(&config.solana_rpc_url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getMultipleAccounts",
+                "params": [config.watched_pool_accounts, {"encoding": "base64"}],
+            }))
+            .send()
+            .await
+            .context("getMultipleAccounts request failed")?;
+
+        let body: serde_json::Value = response.json().await.context("invalid getMultipleAccounts response")?;
+        let accounts = body["result"]["value"].as_array().cloned().unwrap_or_default();
+
+        let mints = Self::watched_pool_mints(config);
+
+        let mut snapshot = Vec::new();
+        for (pubkey, account) in config.watched_pool_accounts.iter().zip(accounts.iter()) {
+            let Some(data_b64) = account["data"][0].as_str() else {
+                continue;
+            };
+            let Ok(data) = BASE64.decode(data_b64) else {
+                continue;
+            };
+            match Self::decode_pool_account(pubkey.clone(), &data, current_slot, &mints) {
+                Ok(pool) => snapshot.push(pool),
+                Err(e) => warn!("Failed to decode pool account snapshot for {}: {}", pubkey, e),
+            }
+        }
+
+        if !snapshot.is_empty() {
+            let mut pools_lock = pools.write().await;
+            *pools_lock = snapshot;
+            info!("Seeded {} pools from getMultipleAccounts snapshot", pools_lock.len());
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the current slot via `getSlot`, used to stamp snapshot-seeded pools.
+    async fn fetch_current_slot(config: &Config, client: &reqwest::Client) -> Result<u64> {
+        let response = client
+            .post(&config.solana_rpc_url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getSlot",
+                "params": [],
+            }))
+            .send()
+            .await
+            .context("getSlot request failed")?;
+
+        let body: serde_json::Value = response.json().await.context("invalid getSlot response")?;
+        body["result"].as_u64().context("getSlot response missing result")
+    }
+
+    /// Build a `pool_address -> (token_a, token_b)` lookup from `config.watched_pool_mints`,
+    /// paired by position with `config.watched_pool_accounts`. The raw AMM account layout
+    /// decoded below carries only reserve amounts, not mint addresses, so the mints have to
+    /// come from config instead.
+    fn watched_pool_mints(config: &Config) -> HashMap<&str, (&str, &str)> {
+        config
+            .watched_pool_accounts
+            .iter()
+            .zip(config.watched_pool_mints.iter())
+            .map(|(address, (token_a, token_b))| (address.as_str(), (token_a.as_str(), token_b.as_str())))
+            .collect()
+    }
+
+    /// Decode a raw AMM pool account into `PoolData`, stamped with the slot at which it
+    /// was observed so stale opportunities can be detected before execution. `mints` looks
+    /// up the pool's token pair by address - a pool with no configured mints is refused
+    /// rather than decoded with blank mints, since quoting against an empty mint address
+    /// is a guaranteed Jupiter rejection anyway.
+    ///
+    /// The exact byte layout is specific to each AMM program; this assumes the common
+    /// reserve-ratio shape (two u64 reserve fields) and is expected to grow program-specific
+    /// branches as more AMMs are onboarded.
+    fn decode_pool_account(
+        pool_address: String,
+        data: &[u8],
+        slot: u64,
+        mints: &HashMap<&str, (&str, &str)>,
+    ) -> Result<PoolData> {
+        anyhow::ensure!(data.len() >= 16, "pool account data too short to decode reserves");
+
+        let (token_a, token_b) = mints
+            .get(pool_address.as_str())
+            .with_context(|| format!("no WATCHED_POOL_MINTS entry configured for pool {}", pool_address))?;
+
+        let liquidity_a = u64::from_le_bytes(data[0..8].try_into().unwrap()) as f64;
+        let liquidity_b = u64::from_le_bytes(data[8..16].try_into().unwrap()) as f64;
+        anyhow::ensure!(liquidity_a > 0.0, "zero reserve_a in pool account");
+
+        Ok(PoolData {
+            pool_address,
+            token_a: token_a.to_string(),
+            token_b: token_b.to_string(),
+            liquidity_a,
+            liquidity_b,
+            price: liquidity_b / liquidity_a,
+            slot,
+            last_updated: chrono::Utc::now().timestamp(),
+        })
     }
 
     /// Detect trading opportunities from pool data
@@ -155,16 +412,23 @@ impl EventLoop {
         config: Arc<Config>,
         pools: Arc<RwLock<Vec<PoolData>>>,
         opportunities: Arc<RwLock<Vec<TradeOpportunity>>>,
+        http_client: reqwest::Client,
+        metrics: Arc<Metrics>,
+        opportunity_tx: mpsc::Sender<TradeOpportunity>,
+        in_flight: Arc<RwLock<IndexSet<String>>>,
     ) -> Result<()> {
         info!("Starting opportunity detection");
-        
+
         loop {
+            let cycle_started_at = tokio::time::Instant::now();
+
             let pools_snapshot = {
                 let pools_lock = pools.read().await;
                 pools_lock.clone()
             };
 
-            let detected = Self::analyze_pools(&config, &pools_snapshot).await;
+            let detected = Self::analyze_pools(&config, &pools_snapshot, &http_client).await;
+            metrics.record_detection_cycle(cycle_started_at.elapsed()).await;
 
             if !detected.is_empty() {
                 let mut opps_lock = opportunities.write().await;
@@ -172,34 +436,115 @@ impl EventLoop {
                 info!("Detected {} trading opportunities", detected.len());
             }
 
+            for opportunity in detected {
+                // Dedup: skip pools that already have an opportunity in flight rather than
+                // enqueuing a second one on top of it.
+                {
+                    let mut in_flight_lock = in_flight.write().await;
+                    if in_flight_lock.contains(&opportunity.pool_address) {
+                        continue;
+                    }
+                    in_flight_lock.insert(opportunity.pool_address.clone());
+                }
+
+                match opportunity_tx.try_send(opportunity) {
+                    Ok(()) => {}
+                    Err(mpsc::error::TrySendError::Full(opportunity)) => {
+                        // Backpressure: the channel is saturated, so skip this opportunity
+                        // rather than blocking detection on a slow execution pipeline.
+                        warn!(
+                            "Opportunity channel full, dropping opportunity for {}",
+                            opportunity.pool_address
+                        );
+                        in_flight.write().await.shift_remove(&opportunity.pool_address);
+                    }
+                    Err(mpsc::error::TrySendError::Closed(opportunity)) => {
+                        error!("Opportunity channel closed, no execution workers running");
+                        in_flight.write().await.shift_remove(&opportunity.pool_address);
+                    }
+                }
+            }
+
             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
         }
     }
 
     /// Analyze pools for arbitrage opportunities
+    ///
+    /// Prices each candidate pool against a real Jupiter quote rather than the
+    /// `price * 0.001` placeholder. `expected_amount_out`/`expected_profit` come from the
+    /// quote's `out_amount` with no slippage deducted; the slippage-buffered worst-case check
+    /// is `Sniper::verify_profitability`, run before execution. Pools whose quote times out
+    /// or errors are skipped rather than blocking the rest of the batch.
+    ///
+    /// When `config.enable_price_corroboration` is set, each pool's reserve-ratio price is
+    /// corroborated against a fallback oracle source before quoting, so a single stale or
+    /// manipulated AMM price can't reach the trade path on its own; see
+    /// `price_feed::corroborate_price`. The only fallback source wired up
+    /// (`PythOracleSource`) is currently a placeholder that's always unavailable, which would
+    /// make corroboration skip every pool forever - so corroboration defaults to disabled,
+    /// and the primary price is trusted on its own until a real fallback is configured.
     async fn analyze_pools(
         config: &Config,
         pools: &[PoolData],
+        http_client: &reqwest::Client,
     ) -> Vec<TradeOpportunity> {
         let mut opportunities = Vec::new();
+        let primary_source = price_feed::ReserveRatioSource;
+        let fallback_source = price_feed::PythOracleSource::new(http_client.clone());
 
-        // Simple price difference detection logic
-        // In production, this would be more sophisticated
         for pool in pools {
-            if pool.liquidity_a > 0.0 && pool.liquidity_b > 0.0 {
-                let profit_potential = pool.price * 0.001; // Simplified calculation
-                
-                if profit_potential > config.min_profit_threshold {
-                    opportunities.push(TradeOpportunity {
-                        pool_address: pool.pool_address.clone(),
-                        token_in: pool.token_a.clone(),
-                        token_out: pool.token_b.clone(),
-                        amount_in: 1.0,
-                        expected_amount_out: pool.price,
-                        expected_profit: profit_potential,
-                        timestamp: chrono::Utc::now().timestamp(),
-                    });
+            let primary_price = primary_source.price(pool).await.unwrap_or(None);
+
+            let corroborated_price = if config.enable_price_corroboration {
+                price_feed::corroborate_price(
+                    pool,
+                    primary_price,
+                    &fallback_source,
+                    config.max_price_deviation_bps,
+                )
+                .await
+            } else {
+                primary_price.filter(|p| *p > 0.0)
+            };
+
+            if corroborated_price.is_none() {
+                continue;
+            }
+
+            let amount_in_lamports = (config.trade_sol_amount * 1e9) as u64;
+            let quote = match crate::sniper::fetch_jupiter_quote(
+                config,
+                http_client,
+                &pool.token_a,
+                &pool.token_b,
+                amount_in_lamports,
+                config.default_swap_mode,
+            )
+            .await
+            {
+                Ok(quote) => quote,
+                Err(e) => {
+                    warn!("Skipping pool {}: Jupiter quote failed: {}", pool.pool_address, e);
+                    continue;
                 }
+            };
+
+            let out_amount: f64 = quote.out_amount.parse().unwrap_or(0.0) / 1e9;
+            let profit_potential = out_amount - config.trade_sol_amount;
+
+            if profit_potential > config.min_profit_threshold {
+                opportunities.push(TradeOpportunity {
+                    pool_address: pool.pool_address.clone(),
+                    token_in: pool.token_a.clone(),
+                    token_out: pool.token_b.clone(),
+                    amount_in: config.trade_sol_amount,
+                    expected_amount_out: out_amount,
+                    expected_profit: profit_potential,
+                    timestamp: chrono::Utc::now().timestamp(),
+                    slot: pool.slot,
+                    swap_mode: config.default_swap_mode,
+                });
             }
         }
 
@@ -212,6 +557,17 @@ impl EventLoop {
         pools_lock.clone()
     }
 
+    /// Get a shared handle to the live pool state, for components (like `Sniper`'s
+    /// pre-trade staleness guard) that need to read the latest observed slot directly.
+    pub fn pools_handle(&self) -> Arc<RwLock<Vec<PoolData>>> {
+        self.pools.clone()
+    }
+
+    /// Get the shared metrics handle.
+    pub fn metrics_handle(&self) -> Arc<Metrics> {
+        self.metrics.clone()
+    }
+
     /// Get detected opportunities
     pub async fn get_opportunities(&self) -> Vec<TradeOpportunity> {
         let opps_lock = self.opportunities.read().await;
@@ -2,12 +2,23 @@
 
 pub mod config;
 pub mod event_loop;
+pub mod metrics;
+pub mod price_feed;
+pub mod quote_cache;
+pub mod router;
+pub mod safety;
+pub mod simulation;
 pub mod sniper;
 pub mod ai_model;
 pub mod utils;
 
 // Re-export commonly used types
-pub use config::Config;
+pub use config::{Config, ExecutionMode, SwapMode};
 pub use event_loop::{EventLoop, LiquidityPool};
+pub use metrics::Metrics;
+pub use price_feed::PriceSource;
+pub use quote_cache::JupiterQuoteCache;
+pub use router::SwapRouter;
+pub use safety::SafetyError;
 pub use sniper::Sniper;
 pub use ai_model::AIModel;
@@ -1,12 +1,18 @@
 mod config;
 mod event_loop;
+mod metrics;
+mod price_feed;
+mod quote_cache;
+mod router;
+mod safety;
+mod simulation;
 mod sniper;
 mod utils;
 
 use anyhow::Result;
 use axum::{
     extract::State,
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Json},
     routing::{get, post},
     Router,
@@ -17,10 +23,11 @@ use tower_http::{
     cors::CorsLayer,
     services::{ServeDir, ServeFile},
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use config::Config;
+use config::{Config, ExecutionMode};
 use event_loop::EventLoop;
+use metrics::Metrics;
 use sniper::Sniper;
 use utils::HealthCheck;
 
@@ -30,6 +37,7 @@ struct AppState {
     event_loop: Arc<EventLoop>,
     sniper: Arc<Sniper>,
     trade_history: Arc<RwLock<Vec<sniper::TradeResult>>>,
+    metrics: Arc<Metrics>,
 }
 
 #[tokio::main]
@@ -51,9 +59,20 @@ async fn main() -> Result<()> {
     info!("Configuration loaded successfully");
     info!("Server will start on {}:{}", config.server_host, config.server_port);
 
+    match config.execution_mode {
+        ExecutionMode::Mock => info!(
+            "=== EXECUTION MODE: MOCK - router quotes and swaps are fabricated; pool monitoring and detection still use live endpoints ==="
+        ),
+        ExecutionMode::DryRun => info!(
+            "=== EXECUTION MODE: DRY RUN - real quotes are fetched, swaps stop before signing ==="
+        ),
+        ExecutionMode::Live => warn!("=== EXECUTION MODE: LIVE - trades will be signed and submitted with real funds ==="),
+    }
+
     // Initialize components
-    let event_loop = Arc::new(EventLoop::new(config.clone()));
-    let sniper = Arc::new(Sniper::new(config.clone()));
+    let metrics = Arc::new(Metrics::new());
+    let event_loop = Arc::new(EventLoop::new(config.clone(), metrics.clone()));
+    let sniper = Arc::new(Sniper::new(config.clone(), event_loop.pools_handle(), metrics.clone()));
 
     // Create app state
     let state = AppState {
@@ -61,6 +80,7 @@ async fn main() -> Result<()> {
         event_loop: event_loop.clone(),
         sniper: sniper.clone(),
         trade_history: Arc::new(RwLock::new(Vec::new())),
+        metrics: metrics.clone(),
     };
 
     // Start background tasks
@@ -75,6 +95,52 @@ async fn main() -> Result<()> {
 
     info!("Event loop started");
 
+    // Spawn the execution worker pool: detection and execution are decoupled by the
+    // opportunity channel, so a slow swap never stalls detection.
+    let opportunity_rx = event_loop
+        .take_opportunity_receiver()
+        .expect("opportunity receiver already taken");
+    let opportunity_rx = Arc::new(tokio::sync::Mutex::new(opportunity_rx));
+
+    for worker_id in 0..config.execution_concurrency {
+        let opportunity_rx = opportunity_rx.clone();
+        let sniper = sniper.clone();
+        let event_loop = event_loop.clone();
+        let trade_history = state.trade_history.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let opportunity = { opportunity_rx.lock().await.recv().await };
+                let Some(opportunity) = opportunity else {
+                    break;
+                };
+
+                let pool_address = opportunity.pool_address.clone();
+                match sniper.execute_trade(&opportunity).await {
+                    Ok(result) => {
+                        if result.success {
+                            info!(
+                                "[worker {}] Trade successful! Profit: {} SOL, Signature: {:?}",
+                                worker_id, result.actual_profit, result.signature
+                            );
+                        } else {
+                            warn!("[worker {}] Trade failed: {:?}", worker_id, result.error);
+                        }
+                        let mut history = trade_history.write().await;
+                        history.push(result);
+                    }
+                    Err(e) => {
+                        error!("[worker {}] Error executing trade: {}", worker_id, e);
+                    }
+                }
+
+                event_loop.mark_complete(&pool_address).await;
+            }
+        });
+    }
+
+    info!("{} execution workers started", config.execution_concurrency);
+
     // Build API routes
     let api_routes = Router::new()
         .route("/health", get(health_check))
@@ -83,6 +149,8 @@ async fn main() -> Result<()> {
         .route("/opportunities", get(get_opportunities))
         .route("/trades", get(get_trades))
         .route("/execute", post(execute_trade))
+        .route("/metrics", get(get_metrics))
+        .route("/quote-cache", get(get_quote_cache_stats))
         .with_state(state.clone());
 
     // Serve dashboard static files
@@ -148,6 +216,22 @@ async fn get_trades(State(state): State<AppState>) -> impl IntoResponse {
     Json(trades.clone())
 }
 
+async fn get_metrics(
+    State(state): State<AppState>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> impl IntoResponse {
+    if params.get("format").map(String::as_str) == Some("prometheus") {
+        let text = state.metrics.prometheus_text().await;
+        return (StatusCode::OK, [(header::CONTENT_TYPE, "text/plain; version=0.0.4")], text).into_response();
+    }
+
+    Json(state.metrics.snapshot().await).into_response()
+}
+
+async fn get_quote_cache_stats(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.sniper.quote_cache_stats())
+}
+
 async fn execute_trade(
     State(state): State<AppState>,
     Json(opportunity): Json<event_loop::TradeOpportunity>,
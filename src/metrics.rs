@@ -0,0 +1,168 @@
+use hdrhistogram::Histogram;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Histograms cover 1ms-60s round trips at 3 significant figures, wide enough for a
+/// pool fetch, a detection cycle, or a full trade execution.
+const LOWEST_DISCERNIBLE_MS: u64 = 1;
+const HIGHEST_TRACKABLE_MS: u64 = 60_000;
+const SIGNIFICANT_FIGURES: u8 = 3;
+
+/// Latency observability for the three hot loops: pool-fetch, opportunity-detection
+/// cycle time, and trade-execution round-trip. Backed by `hdrhistogram` so percentiles
+/// can be read out cheaply without keeping every raw sample.
+pub struct Metrics {
+    pool_fetch: RwLock<Histogram<u64>>,
+    detection_cycle: RwLock<Histogram<u64>>,
+    trade_execution: RwLock<Histogram<u64>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StageSnapshot {
+    pub count: u64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSnapshot {
+    pub pool_fetch: StageSnapshot,
+    pub detection_cycle: StageSnapshot,
+    pub trade_execution: StageSnapshot,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            pool_fetch: RwLock::new(new_histogram()),
+            detection_cycle: RwLock::new(new_histogram()),
+            trade_execution: RwLock::new(new_histogram()),
+        }
+    }
+
+    /// Record one sample of Moralis/gRPC pool-fetch latency.
+    pub async fn record_pool_fetch(&self, duration: Duration) {
+        record(&self.pool_fetch, duration).await;
+    }
+
+    /// Record one sample of opportunity-detection cycle time.
+    pub async fn record_detection_cycle(&self, duration: Duration) {
+        record(&self.detection_cycle, duration).await;
+    }
+
+    /// Record one sample of trade-execution round-trip time.
+    pub async fn record_trade_execution(&self, duration: Duration) {
+        record(&self.trade_execution, duration).await;
+    }
+
+    /// Snapshot p50/p90/p99/max for each stage.
+    pub async fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            pool_fetch: stage_snapshot(&*self.pool_fetch.read().await),
+            detection_cycle: stage_snapshot(&*self.detection_cycle.read().await),
+            trade_execution: stage_snapshot(&*self.trade_execution.read().await),
+        }
+    }
+
+    /// Render the current snapshot as Prometheus text exposition format.
+    pub async fn prometheus_text(&self) -> String {
+        let snapshot = self.snapshot().await;
+        let mut out = String::new();
+        write_stage(&mut out, "pool_fetch", &snapshot.pool_fetch);
+        write_stage(&mut out, "detection_cycle", &snapshot.detection_cycle);
+        write_stage(&mut out, "trade_execution", &snapshot.trade_execution);
+        out
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn new_histogram() -> Histogram<u64> {
+    Histogram::new_with_bounds(LOWEST_DISCERNIBLE_MS, HIGHEST_TRACKABLE_MS, SIGNIFICANT_FIGURES)
+        .expect("static histogram bounds are valid")
+}
+
+async fn record(histogram: &RwLock<Histogram<u64>>, duration: Duration) {
+    let millis = duration.as_millis().clamp(1, HIGHEST_TRACKABLE_MS as u128) as u64;
+    let _ = histogram.write().await.record(millis);
+}
+
+fn stage_snapshot(histogram: &Histogram<u64>) -> StageSnapshot {
+    StageSnapshot {
+        count: histogram.len(),
+        p50_ms: histogram.value_at_quantile(0.50) as f64,
+        p90_ms: histogram.value_at_quantile(0.90) as f64,
+        p99_ms: histogram.value_at_quantile(0.99) as f64,
+        max_ms: histogram.max() as f64,
+    }
+}
+
+fn write_stage(out: &mut String, name: &str, stage: &StageSnapshot) {
+    let _ = writeln!(out, "sai_hf_bot_latency_ms{{stage=\"{}\",quantile=\"0.5\"}} {}", name, stage.p50_ms);
+    let _ = writeln!(out, "sai_hf_bot_latency_ms{{stage=\"{}\",quantile=\"0.9\"}} {}", name, stage.p90_ms);
+    let _ = writeln!(out, "sai_hf_bot_latency_ms{{stage=\"{}\",quantile=\"0.99\"}} {}", name, stage.p99_ms);
+    let _ = writeln!(out, "sai_hf_bot_latency_ms_max{{stage=\"{}\"}} {}", name, stage.max_ms);
+    let _ = writeln!(out, "sai_hf_bot_latency_ms_count{{stage=\"{}\"}} {}", name, stage.count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn a_fresh_metrics_snapshot_is_all_zero() {
+        let metrics = Metrics::new();
+        let snapshot = metrics.snapshot().await;
+        assert_eq!(snapshot.pool_fetch.count, 0);
+        assert_eq!(snapshot.detection_cycle.count, 0);
+        assert_eq!(snapshot.trade_execution.count, 0);
+    }
+
+    #[tokio::test]
+    async fn recorded_samples_land_in_the_right_stage() {
+        let metrics = Metrics::new();
+        metrics.record_pool_fetch(Duration::from_millis(10)).await;
+        metrics.record_pool_fetch(Duration::from_millis(20)).await;
+        metrics.record_detection_cycle(Duration::from_millis(5)).await;
+
+        let snapshot = metrics.snapshot().await;
+        assert_eq!(snapshot.pool_fetch.count, 2);
+        assert_eq!(snapshot.detection_cycle.count, 1);
+        assert_eq!(snapshot.trade_execution.count, 0);
+        assert_eq!(snapshot.pool_fetch.max_ms, 20.0);
+    }
+
+    #[tokio::test]
+    async fn durations_are_clamped_into_the_trackable_range() {
+        let metrics = Metrics::new();
+        // Zero isn't representable by the histogram (lowest discernible value is 1ms) and
+        // durations far beyond the 60s ceiling shouldn't panic or get silently dropped.
+        metrics.record_trade_execution(Duration::from_millis(0)).await;
+        metrics.record_trade_execution(Duration::from_secs(120)).await;
+
+        let snapshot = metrics.snapshot().await;
+        assert_eq!(snapshot.trade_execution.count, 2);
+        // `max()` reports the highest value in the ceiling's bucket, not the raw ceiling.
+        let ceiling_bucket_max = new_histogram().highest_equivalent(HIGHEST_TRACKABLE_MS);
+        assert_eq!(snapshot.trade_execution.max_ms, ceiling_bucket_max as f64);
+    }
+
+    #[tokio::test]
+    async fn prometheus_text_includes_every_stage() {
+        let metrics = Metrics::new();
+        metrics.record_pool_fetch(Duration::from_millis(15)).await;
+
+        let text = metrics.prometheus_text().await;
+        assert!(text.contains("stage=\"pool_fetch\""));
+        assert!(text.contains("stage=\"detection_cycle\""));
+        assert!(text.contains("stage=\"trade_execution\""));
+    }
+}
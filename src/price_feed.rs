@@ -0,0 +1,221 @@
+use crate::event_loop::PoolData;
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::warn;
+
+/// A source of a pool's token_b/token_a price, independent of how `PoolData` itself was
+/// populated. Used to corroborate the primary AMM reserve-ratio price before trading on it,
+/// so a single manipulated or stale source can't move the bot on its own.
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    /// Human-readable name, used in logs when sources disagree.
+    fn name(&self) -> &str;
+
+    /// Return the current price for `pool`, or `None` if this source has no data for it.
+    async fn price(&self, pool: &PoolData) -> Result<Option<f64>>;
+
+    /// Whether this is a real, wired-up feed rather than a stub. `corroborate_price` treats
+    /// an unavailable fallback as "corroboration is impossible" and skips the pool, rather
+    /// than letting a stub's permanent `Ok(None)` silently collapse into "trust the primary
+    /// unconditionally" - which would defeat the point of having a second source at all.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Primary price source: the AMM's own reserve ratio, recomputed from `PoolData` rather
+/// than trusted off the `price` field directly.
+pub struct ReserveRatioSource;
+
+#[async_trait]
+impl PriceSource for ReserveRatioSource {
+    fn name(&self) -> &str {
+        "reserve_ratio"
+    }
+
+    async fn price(&self, pool: &PoolData) -> Result<Option<f64>> {
+        if pool.liquidity_a <= 0.0 || pool.liquidity_b <= 0.0 {
+            return Ok(None);
+        }
+        Ok(Some(pool.liquidity_b / pool.liquidity_a))
+    }
+}
+
+/// Fallback oracle source backed by a Pyth-style price feed.
+///
+/// Placeholder: no real Pyth client is wired up yet, so this always reports no data and
+/// `is_available` returns `false`. Until a real feed is plugged in, that makes
+/// `corroborate_price` skip every pool instead of silently trusting the primary source
+/// unconditionally - a stubbed-out second source can't actually guard against a stale or
+/// manipulated primary, so it shouldn't be treated as if it does.
+pub struct PythOracleSource {
+    http_client: reqwest::Client,
+}
+
+impl PythOracleSource {
+    pub fn new(http_client: reqwest::Client) -> Self {
+        Self { http_client }
+    }
+}
+
+#[async_trait]
+impl PriceSource for PythOracleSource {
+    fn name(&self) -> &str {
+        "pyth_oracle"
+    }
+
+    async fn price(&self, pool: &PoolData) -> Result<Option<f64>> {
+        let _ = (&self.http_client, pool);
+        warn!("Pyth oracle price feed is a placeholder - no corroborating price available");
+        Ok(None)
+    }
+
+    fn is_available(&self) -> bool {
+        false
+    }
+}
+
+/// Corroborate `primary_price` for `pool` against `fallback`, within `max_price_deviation_bps`.
+///
+/// - If `fallback` isn't a real wired-up source (`is_available() == false`), corroboration is
+///   impossible and the pool is skipped - never silently falls back to trusting the primary
+///   alone, since that would defeat the purpose of having a second source at all.
+/// - If both sources agree within tolerance, the primary is used.
+/// - If they disagree beyond tolerance, the fallback is trusted instead, since the primary
+///   is the one suspected of being stale or manipulated.
+/// - If only one source has data for this particular pool, that source is used as-is.
+/// - If neither has data, the pool is skipped (`None`) rather than trading on nothing.
+pub async fn corroborate_price(
+    pool: &PoolData,
+    primary_price: Option<f64>,
+    fallback: &dyn PriceSource,
+    max_price_deviation_bps: u16,
+) -> Option<f64> {
+    if !fallback.is_available() {
+        warn!(
+            "Fallback price source {} is not wired up, cannot corroborate pool {}, skipping",
+            fallback.name(),
+            pool.pool_address
+        );
+        return None;
+    }
+
+    let primary_price = primary_price.filter(|p| *p > 0.0);
+
+    let fallback_price = match fallback.price(pool).await {
+        Ok(p) => p.filter(|p| *p > 0.0),
+        Err(e) => {
+            warn!(
+                "Fallback price source {} errored for pool {}: {}",
+                fallback.name(),
+                pool.pool_address,
+                e
+            );
+            None
+        }
+    };
+
+    match (primary_price, fallback_price) {
+        (Some(primary), Some(secondary)) => {
+            let deviation_bps = ((primary - secondary).abs() / primary * 10_000.0) as u64;
+            if deviation_bps <= max_price_deviation_bps as u64 {
+                Some(primary)
+            } else {
+                warn!(
+                    "Pool {} price disagreement: reserve_ratio={:.9} vs {}={:.9} ({}bps > {}bps tolerance), falling back to {}",
+                    pool.pool_address,
+                    primary,
+                    fallback.name(),
+                    secondary,
+                    deviation_bps,
+                    max_price_deviation_bps,
+                    fallback.name()
+                );
+                Some(secondary)
+            }
+        }
+        (Some(primary), None) => Some(primary),
+        (None, Some(secondary)) => {
+            warn!(
+                "Pool {} has no usable primary price, falling back to {}",
+                pool.pool_address,
+                fallback.name()
+            );
+            Some(secondary)
+        }
+        (None, None) => {
+            warn!("Pool {} has no usable price from any source, skipping", pool.pool_address);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_pool() -> PoolData {
+        PoolData {
+            pool_address: "pool1".to_string(),
+            token_a: "SOL".to_string(),
+            token_b: "USDC".to_string(),
+            liquidity_a: 1_000.0,
+            liquidity_b: 1_020.0,
+            price: 1.02,
+            last_updated: 0,
+            slot: 1,
+        }
+    }
+
+    /// A fallback source with a real, fixed price - unlike `PythOracleSource`, it's
+    /// available, so `corroborate_price` is allowed to use (or disagree with) it.
+    struct FixedPriceSource(Option<f64>);
+
+    #[async_trait]
+    impl PriceSource for FixedPriceSource {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn price(&self, _pool: &PoolData) -> Result<Option<f64>> {
+            Ok(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn unavailable_fallback_skips_the_pool_instead_of_trusting_primary_alone() {
+        let pool = test_pool();
+        let fallback = PythOracleSource::new(reqwest::Client::new());
+        assert!(!fallback.is_available());
+
+        let result = corroborate_price(&pool, Some(1.02), &fallback, 200).await;
+        assert_eq!(result, None);
+    }
+
+    #[tokio::test]
+    async fn agreeing_sources_use_the_primary() {
+        let pool = test_pool();
+        let fallback = FixedPriceSource(Some(1.021));
+
+        let result = corroborate_price(&pool, Some(1.02), &fallback, 200).await;
+        assert_eq!(result, Some(1.02));
+    }
+
+    #[tokio::test]
+    async fn disagreeing_sources_fall_back_to_the_secondary() {
+        let pool = test_pool();
+        let fallback = FixedPriceSource(Some(2.0));
+
+        let result = corroborate_price(&pool, Some(1.02), &fallback, 200).await;
+        assert_eq!(result, Some(2.0));
+    }
+
+    #[tokio::test]
+    async fn no_usable_price_from_either_source_skips_the_pool() {
+        let pool = test_pool();
+        let fallback = FixedPriceSource(None);
+
+        let result = corroborate_price(&pool, None, &fallback, 200).await;
+        assert_eq!(result, None);
+    }
+}
@@ -0,0 +1,168 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
+
+/// Per-pair best observed price (input-per-output tokens; lower is better).
+struct Entry {
+    best_price: Arc<Mutex<f64>>,
+}
+
+/// Outcome of consulting the cache before issuing a network quote for a pair.
+pub enum CacheLookup {
+    /// A pair nobody has quoted yet. The per-pair mutex is already held by the caller:
+    /// run the quote, write the resulting price into the guard, then drop it to release
+    /// any sibling callers that were waiting on `check` for this same pair.
+    FirstRequest(OwnedMutexGuard<f64>),
+    /// A pair that's already been primed. Run the quote freely (concurrently with any
+    /// other in-flight quote for this pair), then call `record_price` with the result.
+    Proceed(Arc<Mutex<f64>>),
+    /// The cached best price for this pair is already worse than `min_acceptable_price`;
+    /// skip the network call entirely.
+    BadPrice(f64),
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct QuoteCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Caches the lowest observed Jupiter price per (input_mint, output_mint) pair.
+///
+/// Under a fast sniping loop, naively quoting every cycle hammers Jupiter with redundant
+/// requests for the same pairs. This cache lets `JupiterRouter::quote` early-out on pairs
+/// that are already known to be unprofitable, and serializes only the very first quote
+/// for a brand new pair so concurrent callers don't all race the network before any price
+/// is known; once a pair is primed, later quotes for it run concurrently as normal.
+pub struct JupiterQuoteCache {
+    entries: RwLock<HashMap<(String, String), Entry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl JupiterQuoteCache {
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Consult the cache for `(input_mint, output_mint)` before issuing a network quote.
+    pub async fn check(&self, input_mint: &str, output_mint: &str, min_acceptable_price: f64) -> CacheLookup {
+        let key = (input_mint.to_string(), output_mint.to_string());
+
+        let existing = {
+            let entries = self.entries.read().await;
+            entries.get(&key).map(|entry| entry.best_price.clone())
+        };
+
+        if let Some(best_price) = existing {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            let current = *best_price.lock().await;
+            if current < f64::MAX && current > min_acceptable_price {
+                return CacheLookup::BadPrice(current);
+            }
+            return CacheLookup::Proceed(best_price);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let best_price = {
+            let mut entries = self.entries.write().await;
+            entries
+                .entry(key)
+                .or_insert_with(|| Entry { best_price: Arc::new(Mutex::new(f64::MAX)) })
+                .best_price
+                .clone()
+        };
+
+        let guard = best_price.clone().lock_owned().await;
+        if *guard < f64::MAX {
+            // A sibling call raced us, inserted the entry, and already primed it with a
+            // real price while we were waiting for the lock - treat it like a warm pair.
+            let current = *guard;
+            drop(guard);
+            if current > min_acceptable_price {
+                return CacheLookup::BadPrice(current);
+            }
+            return CacheLookup::Proceed(best_price);
+        }
+
+        CacheLookup::FirstRequest(guard)
+    }
+
+    /// Record a freshly observed price for a pair, keeping the minimum (best) seen.
+    pub async fn record_price(&self, best_price: &Arc<Mutex<f64>>, observed_price: f64) {
+        let mut guard = best_price.lock().await;
+        if observed_price < *guard {
+            *guard = observed_price;
+        }
+    }
+
+    pub fn stats(&self) -> QuoteCacheStats {
+        QuoteCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for JupiterQuoteCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn first_request_primes_the_pair() {
+        let cache = JupiterQuoteCache::new();
+        match cache.check("SOL", "USDC", 0.9).await {
+            CacheLookup::FirstRequest(mut guard) => *guard = 0.5,
+            other => panic!("expected FirstRequest, got a cached result: {}", other_variant(&other)),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_price_above_the_threshold_is_bad_price() {
+        let cache = JupiterQuoteCache::new();
+        // A price worse (higher input-per-output) than the caller will ever accept.
+        match cache.check("SOL", "USDC", 0.9).await {
+            CacheLookup::FirstRequest(mut guard) => *guard = 1.5,
+            _ => unreachable!(),
+        };
+
+        match cache.check("SOL", "USDC", 0.9).await {
+            CacheLookup::BadPrice(price) => assert_eq!(price, 1.5),
+            other => panic!("expected BadPrice for a price above the threshold, got {}", other_variant(&other)),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_price_at_or_below_the_threshold_proceeds() {
+        let cache = JupiterQuoteCache::new();
+        match cache.check("SOL", "USDC", 0.9).await {
+            CacheLookup::FirstRequest(mut guard) => *guard = 0.5,
+            _ => unreachable!(),
+        };
+
+        match cache.check("SOL", "USDC", 0.9).await {
+            CacheLookup::Proceed(best_price) => assert_eq!(*best_price.lock().await, 0.5),
+            other => panic!("expected Proceed for a price at or below the threshold, got {}", other_variant(&other)),
+        }
+    }
+
+    fn other_variant(lookup: &CacheLookup) -> &'static str {
+        match lookup {
+            CacheLookup::FirstRequest(_) => "FirstRequest",
+            CacheLookup::Proceed(_) => "Proceed",
+            CacheLookup::BadPrice(_) => "BadPrice",
+        }
+    }
+}
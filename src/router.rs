@@ -0,0 +1,675 @@
+use crate::config::{Config, ExecutionMode, SwapMode};
+use crate::event_loop::TradeOpportunity;
+use crate::quote_cache::{CacheLookup, JupiterQuoteCache, QuoteCacheStats};
+use crate::sniper::{self, JupiterQuote, TradeResult};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Signer;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// A quote obtained from a `SwapRouter`, carrying whatever provider-specific data that same
+/// router's `swap` needs to execute it.
+#[derive(Debug, Clone)]
+pub enum Quote {
+    Jupiter(JupiterQuote),
+    Sanctum(SanctumQuote),
+}
+
+impl Quote {
+    /// Quoted output amount in whole tokens (lamports / 1e9), the unit `Sniper` compares
+    /// quotes in when picking the best router.
+    pub fn out_amount(&self) -> f64 {
+        match self {
+            Quote::Jupiter(q) => q.out_amount.parse().unwrap_or(0.0) / 1e9,
+            Quote::Sanctum(q) => q.out_amount.parse().unwrap_or(0.0) / 1e9,
+        }
+    }
+
+    /// Quoted input amount in whole tokens.
+    pub fn in_amount(&self) -> f64 {
+        match self {
+            Quote::Jupiter(q) => q.in_amount.parse().unwrap_or(0.0) / 1e9,
+            Quote::Sanctum(q) => q.in_amount.parse().unwrap_or(0.0) / 1e9,
+        }
+    }
+
+    /// The aggregator's own worst-case fill guarantee, in whole tokens: the minimum
+    /// acceptable `out_amount` for `ExactIn`, or the maximum acceptable `in_amount` for
+    /// `ExactOut`.
+    pub fn other_amount_threshold(&self) -> f64 {
+        match self {
+            Quote::Jupiter(q) => q.other_amount_threshold.parse().unwrap_or(0.0) / 1e9,
+            Quote::Sanctum(q) => q.other_amount_threshold.parse().unwrap_or(0.0) / 1e9,
+        }
+    }
+
+    /// Whether this quote solves for a fixed input or a fixed output.
+    pub fn swap_mode(&self) -> SwapMode {
+        let raw = match self {
+            Quote::Jupiter(q) => &q.swap_mode,
+            Quote::Sanctum(q) => &q.swap_mode,
+        };
+        SwapMode::from_str(raw).unwrap_or(SwapMode::ExactIn)
+    }
+}
+
+/// Quote and execute a swap through a specific aggregator backend.
+///
+/// `Sniper::execute_trade` queries every configured router for a quote, picks the one with
+/// the best `out_amount`, and executes on that router, falling back to the next-best
+/// router if the winning swap transaction itself fails.
+#[async_trait]
+pub trait SwapRouter: Send + Sync {
+    /// Router name, used in logs and to match against `enabled_routers`.
+    fn name(&self) -> &str;
+
+    async fn quote(&self, opportunity: &TradeOpportunity) -> Result<Quote>;
+
+    async fn swap(&self, quote: &Quote) -> Result<TradeResult>;
+}
+
+/// Routes through Jupiter's v6 aggregator - the bot's original, general-purpose path.
+///
+/// Owns the per-pair `JupiterQuoteCache` so repeated quoting for the same mint pair doesn't
+/// hammer Jupiter with redundant requests (see `quote_cache.rs`).
+pub struct JupiterRouter {
+    config: Arc<Config>,
+    http_client: reqwest::Client,
+    quote_cache: Arc<JupiterQuoteCache>,
+}
+
+impl JupiterRouter {
+    pub fn new(config: Arc<Config>, http_client: reqwest::Client) -> Self {
+        Self {
+            config,
+            http_client,
+            quote_cache: Arc::new(JupiterQuoteCache::new()),
+        }
+    }
+
+    /// Cache hit/miss counters for the Jupiter quote cache, for telemetry.
+    pub fn quote_cache_stats(&self) -> QuoteCacheStats {
+        self.quote_cache.stats()
+    }
+}
+
+#[async_trait]
+impl SwapRouter for JupiterRouter {
+    fn name(&self) -> &str {
+        "jupiter"
+    }
+
+    /// In `Mock` mode returns a deterministic placeholder without touching the network.
+    /// ExactOut opportunities are quoted directly, without the cache. For ExactIn it
+    /// consults the per-pair `quote_cache` first: pairs whose best-ever price is
+    /// already worse than `min_profit_threshold` short-circuit without touching the
+    /// network, and a pair seen for the first time has its lone initial quote serialized
+    /// against siblings via the cache's per-pair mutex. A failed fetch is always a hard
+    /// error - no silent placeholder substitution outside of `Mock`.
+    async fn quote(&self, opportunity: &TradeOpportunity) -> Result<Quote> {
+        if self.config.execution_mode == ExecutionMode::Mock {
+            return Ok(Quote::Jupiter(create_placeholder_quote(&self.config, opportunity)));
+        }
+
+        // The quote cache's running-minimum price assumes ExactIn semantics (price =
+        // in/out), so ExactOut opportunities skip it and quote directly.
+        if opportunity.swap_mode == SwapMode::ExactOut {
+            let amount_out_lamports = (opportunity.expected_amount_out * 1e9) as u64;
+            let quote = sniper::fetch_jupiter_quote(
+                &self.config,
+                &self.http_client,
+                &opportunity.token_in,
+                &opportunity.token_out,
+                amount_out_lamports,
+                SwapMode::ExactOut,
+            )
+            .await?;
+            return Ok(Quote::Jupiter(quote));
+        }
+
+        let amount_in_lamports = (opportunity.amount_in * 1e9) as u64;
+        // The highest input-per-output price that still clears the profit threshold for
+        // this trade size: profit = amount_in * (1/price - 1) >= min_profit_threshold.
+        let min_acceptable_price =
+            opportunity.amount_in / (opportunity.amount_in + self.config.min_profit_threshold);
+
+        match self
+            .quote_cache
+            .check(&opportunity.token_in, &opportunity.token_out, min_acceptable_price)
+            .await
+        {
+            CacheLookup::BadPrice(best_price) => Err(anyhow!(
+                "cached best price {:.9} is below profit threshold",
+                best_price
+            )),
+            CacheLookup::FirstRequest(mut guard) => {
+                let quote = sniper::fetch_jupiter_quote(
+                    &self.config,
+                    &self.http_client,
+                    &opportunity.token_in,
+                    &opportunity.token_out,
+                    amount_in_lamports,
+                    SwapMode::ExactIn,
+                )
+                .await?;
+                let price = effective_price(&quote);
+                if price < *guard {
+                    *guard = price;
+                }
+                drop(guard);
+                Ok(Quote::Jupiter(quote))
+            }
+            CacheLookup::Proceed(best_price) => {
+                let quote = sniper::fetch_jupiter_quote(
+                    &self.config,
+                    &self.http_client,
+                    &opportunity.token_in,
+                    &opportunity.token_out,
+                    amount_in_lamports,
+                    SwapMode::ExactIn,
+                )
+                .await?;
+                self.quote_cache.record_price(&best_price, effective_price(&quote)).await;
+                Ok(Quote::Jupiter(quote))
+            }
+        }
+    }
+
+    /// In `Mock` mode reports a fabricated success without touching the network. Otherwise
+    /// builds the swap against the quote's exact route and resolves any address lookup
+    /// tables it references; in `DryRun` it stops there and reports the would-be result
+    /// without ever signing or submitting, while `Live` signs, submits, and waits for
+    /// confirmation before reading back the real on-chain amounts.
+    async fn swap(&self, quote: &Quote) -> Result<TradeResult> {
+        let Quote::Jupiter(quote) = quote else {
+            return Err(anyhow!("JupiterRouter received a non-Jupiter quote"));
+        };
+
+        let in_amount: f64 = quote.in_amount.parse().unwrap_or(0.0) / 1e9;
+        let quoted_out_amount: f64 = quote.out_amount.parse().unwrap_or(0.0) / 1e9;
+
+        if self.config.execution_mode == ExecutionMode::Mock {
+            return Ok(TradeResult {
+                success: true,
+                signature: Some("mock_signature".to_string()),
+                amount_in: in_amount,
+                amount_out: quoted_out_amount,
+                actual_profit: quoted_out_amount - in_amount,
+                error: None,
+            });
+        }
+
+        let keypair = sniper::load_wallet_keypair(&self.config).context("failed to load wallet keypair")?;
+        let wallet_pubkey = keypair.pubkey().to_string();
+
+        let swap = sniper::fetch_jupiter_swap_transaction(&self.config, &self.http_client, quote, &wallet_pubkey)
+            .await
+            .context("failed to build Jupiter swap transaction")?;
+
+        let tx = sniper::decode_versioned_transaction(&swap.swap_transaction)?;
+
+        let lookup_tables =
+            sniper::resolve_address_lookup_tables(&self.config, &self.http_client, &tx.message).await?;
+        info!(
+            "Resolved {} address lookup table(s) for swap transaction",
+            lookup_tables.len()
+        );
+
+        if self.config.execution_mode == ExecutionMode::DryRun {
+            info!(
+                "Dry run: stopping before signing for {} -> {}",
+                quote.input_mint, quote.output_mint
+            );
+            return Ok(TradeResult {
+                success: true,
+                signature: None,
+                amount_in: in_amount,
+                amount_out: quoted_out_amount,
+                actual_profit: quoted_out_amount - in_amount,
+                error: None,
+            });
+        }
+
+        let tx = sniper::sign_versioned_transaction(tx, &keypair)?;
+
+        let signature = sniper::submit_transaction(&self.config, &self.http_client, &tx).await?;
+        info!("Submitted swap transaction: {}", signature);
+
+        let confirmed = sniper::confirm_transaction(&self.config, &self.http_client, &signature).await?;
+        if !confirmed {
+            warn!(
+                "Transaction {} did not confirm within {}ms",
+                signature, self.config.tx_confirmation_timeout_ms
+            );
+            return Ok(TradeResult {
+                success: false,
+                signature: Some(signature),
+                amount_in: in_amount,
+                amount_out: 0.0,
+                actual_profit: 0.0,
+                error: Some("transaction did not confirm in time".to_string()),
+            });
+        }
+
+        let out_amount = match sniper::fetch_onchain_amount_out(
+            &self.config,
+            &self.http_client,
+            &signature,
+            &wallet_pubkey,
+            &quote.output_mint,
+        )
+        .await
+        {
+            Ok(Some(amount)) => amount,
+            Ok(None) => {
+                warn!("Could not read on-chain amount_out for {}, using quoted amount", signature);
+                quote.out_amount.parse().unwrap_or(0.0) / 1e9
+            }
+            Err(e) => {
+                warn!("Failed to read on-chain amount_out for {}: {} (using quoted amount)", signature, e);
+                quote.out_amount.parse().unwrap_or(0.0) / 1e9
+            }
+        };
+
+        Ok(TradeResult {
+            success: true,
+            signature: Some(signature),
+            amount_in,
+            amount_out: out_amount,
+            actual_profit: out_amount - in_amount,
+            error: None,
+        })
+    }
+}
+
+/// A quote's effective price in input-per-output tokens (lower is better). Used to update
+/// the per-pair quote cache's running minimum.
+fn effective_price(quote: &JupiterQuote) -> f64 {
+    let in_amount: f64 = quote.in_amount.parse().unwrap_or(f64::MAX);
+    let out_amount: f64 = quote.out_amount.parse().unwrap_or(0.0);
+    if out_amount <= 0.0 {
+        f64::MAX
+    } else {
+        in_amount / out_amount
+    }
+}
+
+/// Worst-case fill lamports for a placeholder quote's `other_amount_threshold`: the quoted
+/// output amount reduced by `slippage_bps`, matching what Jupiter itself would compute for
+/// an ExactIn quote at that slippage tolerance.
+fn placeholder_other_amount_threshold(out_amount_lamports: u64, slippage_bps: u16) -> u64 {
+    (out_amount_lamports as f64 * (1.0 - slippage_bps as f64 / 10_000.0)).max(0.0) as u64
+}
+
+/// Deterministic placeholder quote for `ExecutionMode::Mock`, priced straight off the
+/// opportunity's own expectations instead of a network call.
+fn create_placeholder_quote(config: &Config, opportunity: &TradeOpportunity) -> JupiterQuote {
+    let out_amount_lamports = (opportunity.expected_amount_out * 1e9) as u64;
+    JupiterQuote {
+        input_mint: opportunity.token_in.clone(),
+        output_mint: opportunity.token_out.clone(),
+        in_amount: ((opportunity.amount_in * 1e9) as u64).to_string(),
+        out_amount: out_amount_lamports.to_string(),
+        other_amount_threshold: placeholder_other_amount_threshold(out_amount_lamports, config.slippage_bps)
+            .to_string(),
+        swap_mode: opportunity.swap_mode.as_jupiter_str().to_string(),
+        slippage_bps: config.slippage_bps,
+        price_impact_pct: "0.1".to_string(),
+        route_plan: Vec::new(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SanctumQuote {
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: String,
+    pub out_amount: String,
+    #[serde(default)]
+    pub other_amount_threshold: String,
+    #[serde(default)]
+    pub swap_mode: String,
+}
+
+/// Deterministic placeholder quote for `ExecutionMode::Mock`, mirroring
+/// `create_placeholder_quote` for the Sanctum route.
+fn create_placeholder_sanctum_quote(config: &Config, opportunity: &TradeOpportunity) -> SanctumQuote {
+    let out_amount_lamports = (opportunity.expected_amount_out * 1e9) as u64;
+    SanctumQuote {
+        input_mint: opportunity.token_in.clone(),
+        output_mint: opportunity.token_out.clone(),
+        in_amount: ((opportunity.amount_in * 1e9) as u64).to_string(),
+        out_amount: out_amount_lamports.to_string(),
+        other_amount_threshold: placeholder_other_amount_threshold(out_amount_lamports, config.slippage_bps)
+            .to_string(),
+        swap_mode: opportunity.swap_mode.as_jupiter_str().to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SanctumSwapRequest {
+    quote: SanctumQuote,
+    signer: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SanctumSwapResponse {
+    tx: String,
+}
+
+/// Routes through Sanctum's LST (liquid-staking-token) aggregator, which typically prices
+/// stake-pool routes (e.g. SOL <-> mSOL, jitoSOL, bSOL) better than general-purpose
+/// aggregators since it knows their exchange-rate math directly instead of sampling pools.
+pub struct SanctumRouter {
+    config: Arc<Config>,
+    http_client: reqwest::Client,
+}
+
+impl SanctumRouter {
+    pub fn new(config: Arc<Config>, http_client: reqwest::Client) -> Self {
+        Self { config, http_client }
+    }
+}
+
+#[async_trait]
+impl SwapRouter for SanctumRouter {
+    fn name(&self) -> &str {
+        "sanctum"
+    }
+
+    async fn quote(&self, opportunity: &TradeOpportunity) -> Result<Quote> {
+        // Sanctum's LST routes are priced directly off a fixed input amount; there's no
+        // equivalent of Jupiter's "solve for input given a desired output" mode wired up
+        // here, so refuse rather than silently quoting against the wrong amount basis.
+        if opportunity.swap_mode == SwapMode::ExactOut {
+            return Err(anyhow!("SanctumRouter does not support ExactOut swaps"));
+        }
+
+        if self.config.execution_mode == ExecutionMode::Mock {
+            return Ok(Quote::Sanctum(create_placeholder_sanctum_quote(&self.config, opportunity)));
+        }
+
+        let amount_in_lamports = (opportunity.amount_in * 1e9) as u64;
+        let url = format!(
+            "{}/quote?input={}&outputLstMint={}&amount={}",
+            self.config.sanctum_api_url, opportunity.token_in, opportunity.token_out, amount_in_lamports
+        );
+
+        let response = self
+            .http_client
+            .get(&url)
+            .send()
+            .await
+            .context("Sanctum /quote request failed")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Sanctum /quote returned status {}", response.status()));
+        }
+
+        let quote: SanctumQuote = response.json().await.context("invalid Sanctum /quote response")?;
+        Ok(Quote::Sanctum(quote))
+    }
+
+    async fn swap(&self, quote: &Quote) -> Result<TradeResult> {
+        let Quote::Sanctum(quote) = quote else {
+            return Err(anyhow!("SanctumRouter received a non-Sanctum quote"));
+        };
+
+        let in_amount: f64 = quote.in_amount.parse().unwrap_or(0.0) / 1e9;
+        let quoted_out_amount: f64 = quote.out_amount.parse().unwrap_or(0.0) / 1e9;
+
+        if self.config.execution_mode == ExecutionMode::Mock {
+            return Ok(TradeResult {
+                success: true,
+                signature: Some("mock_signature".to_string()),
+                amount_in: in_amount,
+                amount_out: quoted_out_amount,
+                actual_profit: quoted_out_amount - in_amount,
+                error: None,
+            });
+        }
+
+        let keypair = sniper::load_wallet_keypair(&self.config).context("failed to load wallet keypair")?;
+        let wallet_pubkey = keypair.pubkey().to_string();
+
+        let url = format!("{}/swap", self.config.sanctum_api_url);
+        let body = SanctumSwapRequest {
+            quote: quote.clone(),
+            signer: wallet_pubkey.clone(),
+        };
+
+        let response = self
+            .http_client
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .context("Sanctum /swap request failed")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Sanctum /swap returned status {}", response.status()));
+        }
+
+        let swap: SanctumSwapResponse = response.json().await.context("invalid Sanctum /swap response")?;
+
+        let tx = sniper::decode_versioned_transaction(&swap.tx)?;
+        let lookup_tables =
+            sniper::resolve_address_lookup_tables(&self.config, &self.http_client, &tx.message).await?;
+        info!(
+            "Resolved {} address lookup table(s) for Sanctum swap transaction",
+            lookup_tables.len()
+        );
+
+        if self.config.execution_mode == ExecutionMode::DryRun {
+            info!(
+                "Dry run: stopping before signing for {} -> {}",
+                quote.input_mint, quote.output_mint
+            );
+            return Ok(TradeResult {
+                success: true,
+                signature: None,
+                amount_in: in_amount,
+                amount_out: quoted_out_amount,
+                actual_profit: quoted_out_amount - in_amount,
+                error: None,
+            });
+        }
+
+        let tx = sniper::sign_versioned_transaction(tx, &keypair)?;
+        let signature = sniper::submit_transaction(&self.config, &self.http_client, &tx).await?;
+        info!("Submitted Sanctum swap transaction: {}", signature);
+
+        let confirmed = sniper::confirm_transaction(&self.config, &self.http_client, &signature).await?;
+        if !confirmed {
+            warn!(
+                "Sanctum transaction {} did not confirm within {}ms",
+                signature, self.config.tx_confirmation_timeout_ms
+            );
+            return Ok(TradeResult {
+                success: false,
+                signature: Some(signature),
+                amount_in: in_amount,
+                amount_out: 0.0,
+                actual_profit: 0.0,
+                error: Some("transaction did not confirm in time".to_string()),
+            });
+        }
+
+        let out_amount = match sniper::fetch_onchain_amount_out(
+            &self.config,
+            &self.http_client,
+            &signature,
+            &wallet_pubkey,
+            &quote.output_mint,
+        )
+        .await
+        {
+            Ok(Some(amount)) => amount,
+            Ok(None) => {
+                warn!("Could not read on-chain amount_out for {}, using quoted amount", signature);
+                quote.out_amount.parse().unwrap_or(0.0) / 1e9
+            }
+            Err(e) => {
+                warn!("Failed to read on-chain amount_out for {}: {} (using quoted amount)", signature, e);
+                quote.out_amount.parse().unwrap_or(0.0) / 1e9
+            }
+        };
+
+        Ok(TradeResult {
+            success: true,
+            signature: Some(signature),
+            amount_in,
+            amount_out: out_amount,
+            actual_profit: out_amount - in_amount,
+            error: None,
+        })
+    }
+}
+
+/// Build the configured set of routers from `config.enabled_routers`, reusing `jupiter_router`
+/// if "jupiter" is enabled so its quote cache is shared with the rest of `Sniper`.
+pub fn build_routers(
+    config: &Arc<Config>,
+    http_client: &reqwest::Client,
+    jupiter_router: Arc<JupiterRouter>,
+) -> Vec<Arc<dyn SwapRouter>> {
+    let mut routers: Vec<Arc<dyn SwapRouter>> = Vec::new();
+
+    for name in &config.enabled_routers {
+        match name.as_str() {
+            "jupiter" => routers.push(jupiter_router.clone()),
+            "sanctum" => routers.push(Arc::new(SanctumRouter::new(config.clone(), http_client.clone()))),
+            other => warn!("Unknown router '{}' in ENABLED_ROUTERS, ignoring", other),
+        }
+    }
+
+    if routers.is_empty() {
+        warn!("No routers enabled, falling back to jupiter");
+        routers.push(jupiter_router);
+    }
+
+    routers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(execution_mode: ExecutionMode, enabled_routers: Vec<String>) -> Config {
+        Config {
+            enabled_routers,
+            execution_mode,
+            ..Default::default()
+        }
+    }
+
+    fn test_opportunity(swap_mode: SwapMode) -> TradeOpportunity {
+        TradeOpportunity {
+            pool_address: "pool1".to_string(),
+            token_in: "SOL".to_string(),
+            token_out: "USDC".to_string(),
+            amount_in: 1.0,
+            expected_amount_out: 1.05,
+            expected_profit: 0.02,
+            timestamp: 0,
+            slot: 1,
+            swap_mode,
+        }
+    }
+
+    #[test]
+    fn quote_accessors_read_through_to_the_jupiter_variant() {
+        let quote = Quote::Jupiter(JupiterQuote {
+            input_mint: "SOL".to_string(),
+            output_mint: "USDC".to_string(),
+            in_amount: "1000000000".to_string(),
+            out_amount: "1050000000".to_string(),
+            other_amount_threshold: "1030000000".to_string(),
+            swap_mode: "ExactIn".to_string(),
+            slippage_bps: 50,
+            price_impact_pct: "0.1".to_string(),
+            route_plan: Vec::new(),
+        });
+
+        assert_eq!(quote.in_amount(), 1.0);
+        assert_eq!(quote.out_amount(), 1.05);
+        assert_eq!(quote.other_amount_threshold(), 1.03);
+        assert_eq!(quote.swap_mode(), SwapMode::ExactIn);
+    }
+
+    #[test]
+    fn quote_accessors_read_through_to_the_sanctum_variant() {
+        let quote = Quote::Sanctum(SanctumQuote {
+            input_mint: "SOL".to_string(),
+            output_mint: "mSOL".to_string(),
+            in_amount: "1000000000".to_string(),
+            out_amount: "950000000".to_string(),
+            other_amount_threshold: "940000000".to_string(),
+            swap_mode: "ExactIn".to_string(),
+        });
+
+        assert_eq!(quote.in_amount(), 1.0);
+        assert_eq!(quote.out_amount(), 0.95);
+        assert_eq!(quote.other_amount_threshold(), 0.94);
+        assert_eq!(quote.swap_mode(), SwapMode::ExactIn);
+    }
+
+    #[tokio::test]
+    async fn sanctum_router_rejects_exact_out_without_touching_the_network() {
+        let config = Arc::new(test_config(ExecutionMode::Live, vec!["sanctum".to_string()]));
+        let router = SanctumRouter::new(config, reqwest::Client::new());
+        let opportunity = test_opportunity(SwapMode::ExactOut);
+
+        let err = router.quote(&opportunity).await.unwrap_err();
+        assert!(err.to_string().contains("does not support ExactOut"));
+    }
+
+    #[tokio::test]
+    async fn sanctum_router_mock_mode_quotes_exact_in() {
+        let config = Arc::new(test_config(ExecutionMode::Mock, vec!["sanctum".to_string()]));
+        let router = SanctumRouter::new(config, reqwest::Client::new());
+        let opportunity = test_opportunity(SwapMode::ExactIn);
+
+        let quote = router.quote(&opportunity).await.expect("mock quote never touches the network");
+        assert!(matches!(quote, Quote::Sanctum(_)));
+    }
+
+    #[tokio::test]
+    async fn jupiter_router_mock_mode_quotes_without_network() {
+        let config = Arc::new(test_config(ExecutionMode::Mock, vec!["jupiter".to_string()]));
+        let router = JupiterRouter::new(config, reqwest::Client::new());
+        let opportunity = test_opportunity(SwapMode::ExactIn);
+
+        let quote = router.quote(&opportunity).await.expect("mock quote never touches the network");
+        assert!(matches!(quote, Quote::Jupiter(_)));
+    }
+
+    #[test]
+    fn build_routers_selects_configured_backends() {
+        let config = Arc::new(test_config(
+            ExecutionMode::Mock,
+            vec!["jupiter".to_string(), "sanctum".to_string()],
+        ));
+        let http_client = reqwest::Client::new();
+        let jupiter_router = Arc::new(JupiterRouter::new(config.clone(), http_client.clone()));
+
+        let routers = build_routers(&config, &http_client, jupiter_router);
+        assert_eq!(routers.len(), 2);
+        assert_eq!(routers[0].name(), "jupiter");
+        assert_eq!(routers[1].name(), "sanctum");
+    }
+
+    #[test]
+    fn build_routers_falls_back_to_jupiter_when_nothing_is_enabled() {
+        let config = Arc::new(test_config(ExecutionMode::Mock, Vec::new()));
+        let http_client = reqwest::Client::new();
+        let jupiter_router = Arc::new(JupiterRouter::new(config.clone(), http_client.clone()));
+
+        let routers = build_routers(&config, &http_client, jupiter_router);
+        assert_eq!(routers.len(), 1);
+        assert_eq!(routers[0].name(), "jupiter");
+    }
+}
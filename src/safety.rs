@@ -0,0 +1,177 @@
+use crate::config::Config;
+use crate::event_loop::{PoolData, TradeOpportunity};
+use std::fmt;
+
+/// Reasons a trade was refused by the pre-trade safety subsystem.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SafetyError {
+    /// Executing this trade would push wallet exposure past `max_position_size_sol`.
+    ExposureExceeded { projected_sol: f64, limit_sol: f64 },
+    /// Expected profit no longer clears `min_profit_threshold`.
+    ProfitBelowThreshold { expected_profit: f64, threshold: f64 },
+    /// The opportunity's pool snapshot is older than the live slot by more than tolerance.
+    StaleOpportunity { opportunity_slot: u64, live_slot: u64, tolerance: u64 },
+    /// The opportunity's pool isn't among the live pools being tracked, so its staleness
+    /// can't be verified at all - e.g. a hand-crafted `/execute` request for an address the
+    /// event loop has never seen.
+    UnknownPool { pool_address: String },
+}
+
+impl fmt::Display for SafetyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SafetyError::ExposureExceeded { projected_sol, limit_sol } => write!(
+                f,
+                "projected exposure {:.4} SOL exceeds max_position_size_sol {:.4} SOL",
+                projected_sol, limit_sol
+            ),
+            SafetyError::ProfitBelowThreshold { expected_profit, threshold } => write!(
+                f,
+                "expected profit {:.6} SOL is below min_profit_threshold {:.6} SOL",
+                expected_profit, threshold
+            ),
+            SafetyError::StaleOpportunity { opportunity_slot, live_slot, tolerance } => write!(
+                f,
+                "opportunity slot {} is {} slots behind live slot {} (tolerance {})",
+                opportunity_slot,
+                live_slot.saturating_sub(*opportunity_slot),
+                live_slot,
+                tolerance
+            ),
+            SafetyError::UnknownPool { pool_address } => write!(
+                f,
+                "pool {} is not among the live tracked pools, cannot verify staleness",
+                pool_address
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SafetyError {}
+
+/// Reject trades that would be unsafe: exposure past `max_position_size_sol`, or
+/// expected profit that no longer clears `min_profit_threshold`.
+pub fn assert_trade_healthy(
+    config: &Config,
+    opportunity: &TradeOpportunity,
+    current_exposure_sol: f64,
+) -> Result<(), SafetyError> {
+    let projected_sol = current_exposure_sol + opportunity.amount_in;
+    if projected_sol > config.max_position_size_sol {
+        return Err(SafetyError::ExposureExceeded {
+            projected_sol,
+            limit_sol: config.max_position_size_sol,
+        });
+    }
+
+    if opportunity.expected_profit < config.min_profit_threshold {
+        return Err(SafetyError::ProfitBelowThreshold {
+            expected_profit: opportunity.expected_profit,
+            threshold: config.min_profit_threshold,
+        });
+    }
+
+    Ok(())
+}
+
+/// Reject trades whose pool snapshot has fallen more than `stale_opportunity_slot_tolerance`
+/// slots behind the pool's current live state, to avoid acting on a view of pool state
+/// that has already moved.
+pub fn assert_not_stale(
+    opportunity: &TradeOpportunity,
+    live_pool: &PoolData,
+    tolerance: u64,
+) -> Result<(), SafetyError> {
+    if live_pool.slot.saturating_sub(opportunity.slot) > tolerance {
+        return Err(SafetyError::StaleOpportunity {
+            opportunity_slot: opportunity.slot,
+            live_slot: live_pool.slot,
+            tolerance,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> Config {
+        Config::default()
+    }
+
+    fn test_opportunity(amount_in: f64, expected_profit: f64, slot: u64) -> TradeOpportunity {
+        TradeOpportunity {
+            pool_address: "pool1".to_string(),
+            token_in: "SOL".to_string(),
+            token_out: "USDC".to_string(),
+            amount_in,
+            expected_amount_out: amount_in + expected_profit,
+            expected_profit,
+            timestamp: 0,
+            slot,
+            swap_mode: crate::config::SwapMode::ExactIn,
+        }
+    }
+
+    fn test_pool(slot: u64) -> PoolData {
+        PoolData {
+            pool_address: "pool1".to_string(),
+            token_a: "SOL".to_string(),
+            token_b: "USDC".to_string(),
+            liquidity_a: 1_000.0,
+            liquidity_b: 1_020.0,
+            price: 1.02,
+            last_updated: 0,
+            slot,
+        }
+    }
+
+    #[test]
+    fn healthy_trade_is_accepted() {
+        let config = test_config();
+        let opportunity = test_opportunity(1.0, 0.02, 1);
+        assert!(assert_trade_healthy(&config, &opportunity, 0.0).is_ok());
+    }
+
+    #[test]
+    fn exposure_past_the_position_limit_is_rejected() {
+        let config = test_config();
+        let opportunity = test_opportunity(1.0, 0.02, 1);
+        let err = assert_trade_healthy(&config, &opportunity, 4.5).unwrap_err();
+        assert_eq!(
+            err,
+            SafetyError::ExposureExceeded { projected_sol: 5.5, limit_sol: 5.0 }
+        );
+    }
+
+    #[test]
+    fn profit_below_threshold_is_rejected() {
+        let config = test_config();
+        let opportunity = test_opportunity(1.0, 0.001, 1);
+        let err = assert_trade_healthy(&config, &opportunity, 0.0).unwrap_err();
+        assert_eq!(
+            err,
+            SafetyError::ProfitBelowThreshold { expected_profit: 0.001, threshold: 0.01 }
+        );
+    }
+
+    #[test]
+    fn pool_within_tolerance_is_not_stale() {
+        let opportunity = test_opportunity(1.0, 0.02, 95);
+        let live_pool = test_pool(100);
+        assert!(assert_not_stale(&opportunity, &live_pool, 10).is_ok());
+    }
+
+    #[test]
+    fn pool_beyond_tolerance_is_stale() {
+        let opportunity = test_opportunity(1.0, 0.02, 50);
+        let live_pool = test_pool(100);
+        let err = assert_not_stale(&opportunity, &live_pool, 10).unwrap_err();
+        assert_eq!(
+            err,
+            SafetyError::StaleOpportunity { opportunity_slot: 50, live_slot: 100, tolerance: 10 }
+        );
+    }
+}
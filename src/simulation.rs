@@ -0,0 +1,82 @@
+use crate::event_loop::PoolData;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// An in-process stand-in for a Solana banks-client/program-test harness.
+///
+/// Tracks SOL-denominated balances for a test wallet and synthetic pool accounts in
+/// memory, and applies swaps against them directly instead of submitting a real
+/// transaction to a validator. Exposes the same shape a real banks-client integration
+/// would (seed accounts, submit a swap, read back balances) so it can be swapped out for
+/// `solana-banks-client` later without touching call sites in `Sniper`.
+pub struct SimulationBank {
+    balances: HashMap<String, f64>,
+    next_signature_id: u64,
+}
+
+impl SimulationBank {
+    pub fn new() -> Self {
+        Self {
+            balances: HashMap::new(),
+            next_signature_id: 0,
+        }
+    }
+
+    /// Seed a test wallet with a starting SOL balance.
+    pub fn seed_wallet(&mut self, wallet: &str, balance_sol: f64) {
+        self.balances.insert(wallet.to_string(), balance_sol);
+    }
+
+    /// Seed a synthetic pool account's reserve, available to be swapped out of.
+    pub fn seed_pool(&mut self, pool: &PoolData) {
+        self.balances.insert(pool.pool_address.clone(), pool.liquidity_b);
+    }
+
+    /// Read back the current balance of an address, defaulting to zero if never seeded.
+    pub fn balance(&self, address: &str) -> f64 {
+        self.balances.get(address).copied().unwrap_or(0.0)
+    }
+
+    /// Submit a swap: debit `amount_in` from `wallet` and credit `amount_out` to it from
+    /// `pool_address`'s reserve, mirroring submitting a transaction to a banks client and
+    /// reading back the resulting balances. Fails if either side has insufficient balance.
+    pub fn submit_swap(
+        &mut self,
+        wallet: &str,
+        pool_address: &str,
+        amount_in: f64,
+        amount_out: f64,
+    ) -> Result<String> {
+        let wallet_balance = self.balance(wallet);
+        anyhow::ensure!(
+            wallet_balance >= amount_in,
+            "wallet {} has insufficient balance ({:.6}) for swap of {:.6}",
+            wallet,
+            wallet_balance,
+            amount_in
+        );
+
+        let pool_balance = self.balance(pool_address);
+        anyhow::ensure!(
+            pool_balance >= amount_out,
+            "pool {} has insufficient reserve ({:.6}) for swap out of {:.6}",
+            pool_address,
+            pool_balance,
+            amount_out
+        );
+
+        self.balances
+            .insert(wallet.to_string(), wallet_balance - amount_in + amount_out);
+        self.balances
+            .insert(pool_address.to_string(), pool_balance - amount_out + amount_in);
+
+        self.next_signature_id += 1;
+        Ok(format!("simulated_signature_{}", self.next_signature_id))
+    }
+}
+
+impl Default for SimulationBank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,9 +1,22 @@
-use crate::config::Config;
-use crate::event_loop::TradeOpportunity;
-use anyhow::Result;
+use crate::config::{Config, SwapMode};
+use crate::event_loop::{PoolData, TradeOpportunity};
+use crate::metrics::Metrics;
+use crate::quote_cache::QuoteCacheStats;
+use crate::router::{self, JupiterRouter, Quote, SwapRouter};
+use crate::safety::{self, SafetyError};
+use crate::simulation::SimulationBank;
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use serde::{Deserialize, Serialize};
+use solana_address_lookup_table_program::state::AddressLookupTable;
+use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::VersionedTransaction;
 use std::sync::Arc;
-use tracing::{error, info, warn};
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeResult {
@@ -16,6 +29,7 @@ pub struct TradeResult {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct JupiterQuote {
     pub input_mint: String,
     pub output_mint: String,
@@ -25,28 +39,398 @@ pub struct JupiterQuote {
     pub swap_mode: String,
     pub slippage_bps: u16,
     pub price_impact_pct: String,
+    /// The exact route this quote was priced on, round-tripped to `/swap` so the built
+    /// transaction swaps through the same AMMs instead of letting Jupiter re-route.
+    #[serde(default)]
+    pub route_plan: Vec<RoutePlanStep>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutePlanStep {
+    pub swap_info: SwapInfo,
+    pub percent: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInfo {
+    pub amm_key: String,
+    pub label: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: String,
+    pub out_amount: String,
+    pub fee_amount: String,
+    pub fee_mint: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct JupiterSwapRequest {
     pub quote_response: JupiterQuote,
     pub user_public_key: String,
+    /// Jupiter's actual field name for this flag, which `rename_all = "camelCase"` alone
+    /// would render as `wrapUnwrapSol` - renamed explicitly to match the real API.
+    #[serde(rename = "wrapAndUnwrapSol")]
     pub wrap_unwrap_sol: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterSwapResponse {
+    pub swap_transaction: String,
+}
+
+/// Fetch a quote from the Jupiter v6 `/quote` endpoint, bounded by `jupiter_timeout_ms`.
+///
+/// Shared by `Sniper` and the event loop's opportunity detection so both paths price
+/// trades against the same live aggregator instead of a toy model. Returns an error
+/// (rather than hanging) if Jupiter doesn't respond within the configured timeout.
+///
+/// `amount_lamports` is the input amount for `SwapMode::ExactIn` and the desired output
+/// amount for `SwapMode::ExactOut` - Jupiter solves for whichever side isn't fixed.
+pub(crate) async fn fetch_jupiter_quote(
+    config: &Config,
+    client: &reqwest::Client,
+    input_mint: &str,
+    output_mint: &str,
+    amount_lamports: u64,
+    swap_mode: SwapMode,
+) -> Result<JupiterQuote> {
+    let url = format!(
+        "{}/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}&swapMode={}",
+        config.jupiter_api_url,
+        input_mint,
+        output_mint,
+        amount_lamports,
+        config.slippage_bps,
+        swap_mode.as_jupiter_str()
+    );
+
+    let request = client.get(&url).send();
+    let response = tokio::time::timeout(Duration::from_millis(config.jupiter_timeout_ms), request)
+        .await
+        .map_err(|_| anyhow!("Jupiter /quote timed out after {}ms", config.jupiter_timeout_ms))??;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Jupiter /quote returned status {}", response.status()));
+    }
+
+    let quote: JupiterQuote = response.json().await?;
+    Ok(quote)
+}
+
+/// Request a serialized swap transaction from the Jupiter v6 `/swap` endpoint for a
+/// previously obtained quote, bounded by `jupiter_timeout_ms`.
+pub(crate) async fn fetch_jupiter_swap_transaction(
+    config: &Config,
+    client: &reqwest::Client,
+    quote: &JupiterQuote,
+    user_public_key: &str,
+) -> Result<JupiterSwapResponse> {
+    let url = format!("{}/swap", config.jupiter_api_url);
+    let body = JupiterSwapRequest {
+        quote_response: quote.clone(),
+        user_public_key: user_public_key.to_string(),
+        wrap_unwrap_sol: true,
+    };
+
+    let request = client.post(&url).json(&body).send();
+    let response = tokio::time::timeout(Duration::from_millis(config.jupiter_timeout_ms), request)
+        .await
+        .map_err(|_| anyhow!("Jupiter /swap timed out after {}ms", config.jupiter_timeout_ms))??;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Jupiter /swap returned status {}", response.status()));
+    }
+
+    let swap: JupiterSwapResponse = response.json().await?;
+    Ok(swap)
+}
+
+/// Load the wallet keypair from `wallet_private_key` (a base58-encoded 64-byte secret key,
+/// the same format `solana-keygen`/Phantom export).
+pub(crate) fn load_wallet_keypair(config: &Config) -> Result<Keypair> {
+    let secret_bytes = bs58::decode(&config.wallet_private_key)
+        .into_vec()
+        .context("wallet_private_key is not valid base58")?;
+    Keypair::from_bytes(&secret_bytes).map_err(|e| anyhow!("invalid wallet keypair: {}", e))
+}
+
+/// Decode the base64-encoded, Jupiter-built swap transaction into a `VersionedTransaction`.
+pub(crate) fn decode_versioned_transaction(swap_transaction_b64: &str) -> Result<VersionedTransaction> {
+    let raw = BASE64
+        .decode(swap_transaction_b64)
+        .context("swap_transaction is not valid base64")?;
+    bincode::deserialize(&raw).context("failed to deserialize VersionedTransaction")
+}
+
+/// Resolve the address lookup tables a v0 message references, fetching each table account
+/// from the RPC so the full set of addresses it expands to is known before submission.
+/// Legacy (non-v0) messages have no lookups and resolve to an empty `Vec`.
+pub(crate) async fn resolve_address_lookup_tables(
+    config: &Config,
+    client: &reqwest::Client,
+    message: &VersionedMessage,
+) -> Result<Vec<AddressLookupTableAccount>> {
+    let VersionedMessage::V0(v0_message) = message else {
+        return Ok(Vec::new());
+    };
+
+    let mut tables = Vec::with_capacity(v0_message.address_table_lookups.len());
+    for lookup in &v0_message.address_table_lookups {
+        let account_data = fetch_account_data(config, client, &lookup.account_key.to_string()).await?;
+        let table = AddressLookupTable::deserialize(&account_data)
+            .context("failed to deserialize address lookup table account")?;
+
+        tables.push(AddressLookupTableAccount {
+            key: lookup.account_key,
+            addresses: table.addresses.to_vec(),
+        });
+    }
+
+    Ok(tables)
+}
+
+/// Fetch and base64-decode an account's raw data via `getAccountInfo`.
+async fn fetch_account_data(config: &Config, client: &reqwest::Client, pubkey: &str) -> Result<Vec<u8>> {
+    let response = client
+        .post(&config.solana_rpc_url)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getAccountInfo",
+            "params": [pubkey, {"encoding": "base64"}],
+        }))
+        .send()
+        .await
+        .context("getAccountInfo request failed")?;
+
+    let body: serde_json::Value = response.json().await.context("invalid getAccountInfo response")?;
+    let data_b64 = body["result"]["value"]["data"][0]
+        .as_str()
+        .context("getAccountInfo response missing account data")?;
+
+    BASE64.decode(data_b64).context("account data is not valid base64")
+}
+
+/// Sign a `VersionedTransaction` built by Jupiter with the wallet keypair. Jupiter places
+/// the fee payer (our wallet) first among the required signers, so the keypair's signature
+/// replaces the placeholder at index 0.
+pub(crate) fn sign_versioned_transaction(
+    mut tx: VersionedTransaction,
+    keypair: &Keypair,
+) -> Result<VersionedTransaction> {
+    let message_bytes = tx.message.serialize();
+    let signature = keypair.sign_message(&message_bytes);
+
+    anyhow::ensure!(
+        !tx.signatures.is_empty(),
+        "swap transaction has no signature slots to fill"
+    );
+    tx.signatures[0] = signature;
+
+    Ok(tx)
+}
+
+/// Submit a signed transaction via `sendTransaction`, returning its signature.
+pub(crate) async fn submit_transaction(
+    config: &Config,
+    client: &reqwest::Client,
+    tx: &VersionedTransaction,
+) -> Result<String> {
+    let raw = bincode::serialize(tx).context("failed to serialize signed transaction")?;
+    let encoded = BASE64.encode(raw);
+
+    let response = client
+        .post(&config.solana_rpc_url)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendTransaction",
+            "params": [encoded, {"encoding": "base64", "skipPreflight": false, "maxRetries": config.max_retries}],
+        }))
+        .send()
+        .await
+        .context("sendTransaction request failed")?;
+
+    let body: serde_json::Value = response.json().await.context("invalid sendTransaction response")?;
+    if let Some(error) = body.get("error") {
+        return Err(anyhow!("sendTransaction returned an error: {}", error));
+    }
+
+    body["result"]
+        .as_str()
+        .map(str::to_string)
+        .context("sendTransaction response missing signature")
+}
+
+/// Poll `getSignatureStatuses` until the transaction confirms, errors on-chain, or
+/// `tx_confirmation_timeout_ms` elapses.
+pub(crate) async fn confirm_transaction(config: &Config, client: &reqwest::Client, signature: &str) -> Result<bool> {
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(config.tx_confirmation_timeout_ms);
+
+    while tokio::time::Instant::now() < deadline {
+        let response = client
+            .post(&config.solana_rpc_url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getSignatureStatuses",
+                "params": [[signature], {"searchTransactionHistory": true}],
+            }))
+            .send()
+            .await
+            .context("getSignatureStatuses request failed")?;
+
+        let body: serde_json::Value = response.json().await.context("invalid getSignatureStatuses response")?;
+        let status = &body["result"]["value"][0];
+
+        if !status.is_null() {
+            if let Some(err) = status.get("err") {
+                if !err.is_null() {
+                    return Err(anyhow!("transaction {} failed on-chain: {}", signature, err));
+                }
+            }
+
+            let confirmations_met = status["confirmationStatus"]
+                .as_str()
+                .map(|s| s == "confirmed" || s == "finalized")
+                .unwrap_or(false);
+            if confirmations_met {
+                return Ok(true);
+            }
+        }
+
+        tokio::time::sleep(Duration::from_millis(config.tx_confirmation_poll_ms)).await;
+    }
+
+    Ok(false)
+}
+
+/// Read back the wallet's actual output-mint balance delta for a confirmed transaction via
+/// `getTransaction`, falling back to `None` if the balance change can't be determined (the
+/// quote's `out_amount` is used instead at the call site).
+pub(crate) async fn fetch_onchain_amount_out(
+    config: &Config,
+    client: &reqwest::Client,
+    signature: &str,
+    wallet_pubkey: &str,
+    output_mint: &str,
+) -> Result<Option<f64>> {
+    let response = client
+        .post(&config.solana_rpc_url)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getTransaction",
+            "params": [signature, {"encoding": "json", "maxSupportedTransactionVersion": 0}],
+        }))
+        .send()
+        .await
+        .context("getTransaction request failed")?;
+
+    let body: serde_json::Value = response.json().await.context("invalid getTransaction response")?;
+    let meta = &body["result"]["meta"];
+    let account_keys = body["result"]["transaction"]["message"]["accountKeys"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    if !account_keys.iter().any(|k| k.as_str() == Some(wallet_pubkey)) {
+        return Ok(None);
+    }
+
+    let balance_for = |balances: &serde_json::Value| -> Option<f64> {
+        balances.as_array()?.iter().find_map(|b| {
+            if b["owner"].as_str()? != wallet_pubkey {
+                return None;
+            }
+            if b["mint"].as_str()? != output_mint {
+                return None;
+            }
+            b["uiTokenAmount"]["uiAmount"].as_f64()
+        })
+    };
+
+    let pre = balance_for(&meta["preTokenBalances"]);
+    let post = balance_for(&meta["postTokenBalances"]);
+    if pre.is_none() && post.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(post.unwrap_or(0.0) - pre.unwrap_or(0.0)))
+}
+
 pub struct Sniper {
     config: Arc<Config>,
-    http_client: reqwest::Client,
+    /// Live pool state shared with the event loop, used to check an opportunity's
+    /// snapshot slot against the pool's current slot before executing.
+    pools: Arc<RwLock<Vec<PoolData>>>,
+    /// SOL currently committed to trades that are in flight, used for the pre-trade
+    /// exposure check.
+    in_flight_exposure_sol: Arc<RwLock<f64>>,
+    metrics: Arc<Metrics>,
+    /// In-process bank for the simulation execution path (tests / CI), bypassing live
+    /// Jupiter and RPC calls entirely. `None` means trades execute against live services.
+    simulation_bank: Option<Arc<Mutex<SimulationBank>>>,
+    /// Every router listed in `enabled_routers`, queried for a quote on each trade; the
+    /// one with the best `out_amount` is executed, with the rest as fallbacks.
+    routers: Vec<Arc<dyn SwapRouter>>,
+    /// Kept alongside `routers` (which it's also a member of) so its quote cache stats can
+    /// be exposed without downcasting a trait object.
+    jupiter_router: Arc<JupiterRouter>,
 }
 
 impl Sniper {
-    pub fn new(config: Arc<Config>) -> Self {
+    pub fn new(config: Arc<Config>, pools: Arc<RwLock<Vec<PoolData>>>, metrics: Arc<Metrics>) -> Self {
+        let http_client = reqwest::Client::new();
+        let jupiter_router = Arc::new(JupiterRouter::new(config.clone(), http_client.clone()));
+        let routers = router::build_routers(&config, &http_client, jupiter_router.clone());
+
+        Self {
+            config,
+            pools,
+            in_flight_exposure_sol: Arc::new(RwLock::new(0.0)),
+            metrics,
+            simulation_bank: None,
+            routers,
+            jupiter_router,
+        }
+    }
+
+    /// Construct a `Sniper` that submits trades against an in-process `SimulationBank`
+    /// instead of a live transaction, so quoting, router selection, and
+    /// `verify_profitability` are all still exercised exactly as on the live path -
+    /// `config.execution_mode` should be `Mock` so router quoting itself never hits the
+    /// network either.
+    pub fn new_simulated(
+        config: Arc<Config>,
+        pools: Arc<RwLock<Vec<PoolData>>>,
+        metrics: Arc<Metrics>,
+        bank: Arc<Mutex<SimulationBank>>,
+    ) -> Self {
+        let http_client = reqwest::Client::new();
+        let jupiter_router = Arc::new(JupiterRouter::new(config.clone(), http_client.clone()));
+        let routers = router::build_routers(&config, &http_client, jupiter_router.clone());
+
         Self {
             config,
-            http_client: reqwest::Client::new(),
+            pools,
+            in_flight_exposure_sol: Arc::new(RwLock::new(0.0)),
+            metrics,
+            simulation_bank: Some(bank),
+            routers,
+            jupiter_router,
         }
     }
 
+    /// Cache hit/miss counters for the Jupiter quote cache, for telemetry.
+    pub fn quote_cache_stats(&self) -> QuoteCacheStats {
+        self.jupiter_router.quote_cache_stats()
+    }
+
     /// Execute a trade based on an opportunity
     pub async fn execute_trade(&self, opportunity: &TradeOpportunity) -> Result<TradeResult> {
         info!(
@@ -54,148 +438,197 @@ impl Sniper {
             opportunity.token_in, opportunity.token_out
         );
 
-        // Step 1: Get Jupiter quote
-        let quote = match self.get_jupiter_quote(opportunity).await {
-            Ok(q) => q,
-            Err(e) => {
-                error!("Failed to get Jupiter quote: {}", e);
+        let started_at = tokio::time::Instant::now();
+        let result = self.execute_trade_guarded(opportunity).await;
+        self.metrics.record_trade_execution(started_at.elapsed()).await;
+
+        result
+    }
+
+    async fn execute_trade_guarded(&self, opportunity: &TradeOpportunity) -> Result<TradeResult> {
+        // Step 0: staleness guard - abort if the pool has moved since this opportunity was
+        // observed. A pool that isn't tracked at all can't be verified for staleness, so it
+        // is refused outright rather than silently let through (e.g. a hand-crafted
+        // `/execute` request for an address the event loop has never seen).
+        let live_pool = self
+            .pools
+            .read()
+            .await
+            .iter()
+            .find(|p| p.pool_address == opportunity.pool_address)
+            .cloned();
+
+        match live_pool {
+            Some(live_pool) => {
+                safety::assert_not_stale(opportunity, &live_pool, self.config.stale_opportunity_slot_tolerance)
+                    .map_err(|e: SafetyError| anyhow!(e))?;
+            }
+            None => {
+                return Err(anyhow!(SafetyError::UnknownPool {
+                    pool_address: opportunity.pool_address.clone(),
+                }));
+            }
+        }
+
+        // Step 0.5: health assertion - refuse trades that would blow past our exposure
+        // limit or that no longer clear the profit threshold. The check and the exposure
+        // reservation must happen under the same write-lock acquisition, or concurrent
+        // execution workers (chunk0-5 spawns `execution_concurrency` of them) can each read
+        // the same stale exposure, each pass the check, and collectively blow past
+        // max_position_size_sol.
+        {
+            let mut exposure = self.in_flight_exposure_sol.write().await;
+            if let Err(e) = safety::assert_trade_healthy(&self.config, opportunity, *exposure) {
+                warn!("Trade rejected by pre-trade health assertion: {}", e);
                 return Ok(TradeResult {
                     success: false,
                     signature: None,
                     amount_in: opportunity.amount_in,
                     amount_out: 0.0,
                     actual_profit: 0.0,
-                    error: Some(format!("Quote failed: {}", e)),
+                    error: Some(e.to_string()),
                 });
             }
-        };
+            *exposure += opportunity.amount_in;
+        }
+
+        let result = self.execute_trade_inner(opportunity).await;
+        *self.in_flight_exposure_sol.write().await -= opportunity.amount_in;
+
+        result
+    }
+
+    async fn execute_trade_inner(&self, opportunity: &TradeOpportunity) -> Result<TradeResult> {
+        // Step 1: quote every configured router, keeping the ones that actually returned one.
+        // The simulation bank only ever replaces the final transaction submission below, so
+        // a test wiring `Sniper::new_simulated` still exercises real quoting, ranking, and
+        // `verify_profitability` - it should run with `ExecutionMode::Mock` so this step
+        // never touches the network.
+        let mut quotes: Vec<(&Arc<dyn SwapRouter>, Quote)> = Vec::new();
+        for router in &self.routers {
+            match router.quote(opportunity).await {
+                Ok(quote) => quotes.push((router, quote)),
+                Err(e) => warn!("{} quote failed: {}", router.name(), e),
+            }
+        }
 
-        // Step 2: Verify the quote meets our profit threshold
-        if !self.verify_profitability(&quote, opportunity) {
-            warn!("Trade no longer profitable after quote");
+        if quotes.is_empty() {
+            warn!("No router returned a quote for {} -> {}", opportunity.token_in, opportunity.token_out);
             return Ok(TradeResult {
                 success: false,
                 signature: None,
                 amount_in: opportunity.amount_in,
                 amount_out: 0.0,
                 actual_profit: 0.0,
-                error: Some("Insufficient profit after quote".to_string()),
+                error: Some("no router returned a quote".to_string()),
             });
         }
 
-        // Step 3: Execute the swap via Jupiter
-        let result = self.execute_jupiter_swap(&quote).await?;
+        // Step 2: rank quotes best-out_amount-first and execute on the winner, falling back
+        // to the next-best router if a quote turns out unprofitable or its swap fails.
+        quotes.sort_by(|a, b| b.1.out_amount().partial_cmp(&a.1.out_amount()).unwrap_or(std::cmp::Ordering::Equal));
 
-        info!("Trade executed successfully: {:?}", result);
-        Ok(result)
-    }
+        for (router, quote) in &quotes {
+            if !self.verify_profitability(quote, opportunity) {
+                warn!("{} quote no longer profitable, skipping", router.name());
+                continue;
+            }
 
-    /// Get a quote from Jupiter aggregator
-    async fn get_jupiter_quote(&self, opportunity: &TradeOpportunity) -> Result<JupiterQuote> {
-        let url = format!(
-            "{}/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}",
-            self.config.jupiter_api_url,
-            opportunity.token_in,
-            opportunity.token_out,
-            (opportunity.amount_in * 1e9) as u64, // Convert to lamports
-            self.config.slippage_bps
-        );
+            let swap_result = match &self.simulation_bank {
+                Some(bank) => self.execute_trade_via_bank(bank, opportunity, quote).await,
+                None => router.swap(quote).await,
+            };
 
-        let response = self.http_client.get(&url).send().await;
-
-        match response {
-            Ok(resp) => {
-                if resp.status().is_success() {
-                    let quote: JupiterQuote = resp.json().await?;
-                    Ok(quote)
-                } else {
-                    warn!("Jupiter API returned error status: {}", resp.status());
-                    // Return a placeholder quote for development
-                    Ok(self.create_placeholder_quote(opportunity))
+            match swap_result {
+                Ok(result) => {
+                    info!("Trade executed successfully via {}: {:?}", router.name(), result);
+                    return Ok(result);
                 }
-            }
-            Err(e) => {
-                warn!("Jupiter API call failed: {} (using placeholder)", e);
-                Ok(self.create_placeholder_quote(opportunity))
+                Err(e) => warn!("{} swap failed, trying next router: {}", router.name(), e),
             }
         }
-    }
-
-    /// Create a placeholder quote for development
-    fn create_placeholder_quote(&self, opportunity: &TradeOpportunity) -> JupiterQuote {
-        JupiterQuote {
-            input_mint: opportunity.token_in.clone(),
-            output_mint: opportunity.token_out.clone(),
-            in_amount: ((opportunity.amount_in * 1e9) as u64).to_string(),
-            out_amount: ((opportunity.expected_amount_out * 1e9) as u64).to_string(),
-            other_amount_threshold: "0".to_string(),
-            swap_mode: "ExactIn".to_string(),
-            slippage_bps: self.config.slippage_bps,
-            price_impact_pct: "0.1".to_string(),
-        }
-    }
 
-    /// Verify that the quote still meets profitability requirements
-    fn verify_profitability(&self, quote: &JupiterQuote, opportunity: &TradeOpportunity) -> bool {
-        let out_amount: f64 = quote.out_amount.parse().unwrap_or(0.0) / 1e9;
-        let in_amount: f64 = quote.in_amount.parse().unwrap_or(1.0) / 1e9;
-        
-        let profit = out_amount - in_amount;
-        
-        profit >= self.config.min_profit_threshold && profit >= opportunity.expected_profit * 0.8
-    }
-
-    /// Execute a swap transaction via Jupiter
-    async fn execute_jupiter_swap(&self, quote: &JupiterQuote) -> Result<TradeResult> {
-        // In production, this would:
-        // 1. Create a swap transaction using Jupiter's /swap endpoint
-        // 2. Sign the transaction with the wallet private key
-        // 3. Submit the transaction to Solana network
-        // 4. Wait for confirmation
-        
-        // For now, return a placeholder result
-        warn!("Jupiter swap execution is a placeholder - no actual transaction sent");
-        
-        let in_amount: f64 = quote.in_amount.parse().unwrap_or(0.0) / 1e9;
-        let out_amount: f64 = quote.out_amount.parse().unwrap_or(0.0) / 1e9;
-        
+        warn!("All routers failed to execute a profitable swap");
         Ok(TradeResult {
-            success: true,
-            signature: Some("placeholder_signature".to_string()),
-            amount_in: in_amount,
-            amount_out: out_amount,
-            actual_profit: out_amount - in_amount,
-            error: None,
+            success: false,
+            signature: None,
+            amount_in: opportunity.amount_in,
+            amount_out: 0.0,
+            actual_profit: 0.0,
+            error: Some("no router could execute a profitable swap".to_string()),
         })
     }
 
-    /// Execute trades in a sniping loop
-    pub async fn snipe_loop(&self, opportunities_rx: tokio::sync::mpsc::Receiver<TradeOpportunity>) {
-        info!("Starting sniper loop");
-        
-        let mut rx = opportunities_rx;
-        
-        while let Some(opportunity) = rx.recv().await {
-            if opportunity.expected_profit >= self.config.min_profit_threshold {
-                match self.execute_trade(&opportunity).await {
-                    Ok(result) => {
-                        if result.success {
-                            info!(
-                                "Trade successful! Profit: {} SOL, Signature: {:?}",
-                                result.actual_profit, result.signature
-                            );
-                        } else {
-                            warn!("Trade failed: {:?}", result.error);
-                        }
-                    }
-                    Err(e) => {
-                        error!("Error executing trade: {}", e);
-                    }
-                }
+    /// Execute a trade against the in-process `SimulationBank` instead of live Jupiter/RPC
+    /// calls: submit the winning quote directly against the bank's synthetic balances and
+    /// read back the resulting wallet balance to fill `actual_profit`, exactly as a real
+    /// banks-client submission and balance read-back would. Quoting, router selection, and
+    /// `verify_profitability` run the same way they do on the live path - only the
+    /// transaction submission itself is swapped for the in-memory bank.
+    async fn execute_trade_via_bank(
+        &self,
+        bank: &Arc<Mutex<SimulationBank>>,
+        opportunity: &TradeOpportunity,
+        quote: &Quote,
+    ) -> Result<TradeResult> {
+        let wallet = &self.config.wallet_private_key;
+        let in_amount = quote.in_amount();
+        let out_amount = quote.out_amount();
+        let mut bank = bank.lock().await;
+        let balance_before = bank.balance(wallet);
+
+        match bank.submit_swap(wallet, &opportunity.pool_address, in_amount, out_amount) {
+            Ok(signature) => {
+                let actual_profit = bank.balance(wallet) - balance_before;
+                info!("Simulated trade executed via in-process bank: {}", signature);
+                Ok(TradeResult {
+                    success: true,
+                    signature: Some(signature),
+                    amount_in: in_amount,
+                    amount_out: out_amount,
+                    actual_profit,
+                    error: None,
+                })
+            }
+            Err(e) => {
+                warn!("Simulated swap rejected by bank: {}", e);
+                Ok(TradeResult {
+                    success: false,
+                    signature: None,
+                    amount_in: in_amount,
+                    amount_out: 0.0,
+                    actual_profit: 0.0,
+                    error: Some(e.to_string()),
+                })
             }
         }
     }
 
+    /// Verify that a router's quote still clears profitability once a worst-case fill is
+    /// assumed. The configured `slippage_buffer_bps` models price drift between quote and
+    /// execution; the quote's own `other_amount_threshold` is the aggregator's own worst-case
+    /// guarantee. Neither is discarded - the stricter of the two always wins, since either
+    /// can be the tighter bound depending on the quote.
+    fn verify_profitability(&self, quote: &Quote, opportunity: &TradeOpportunity) -> bool {
+        let buffer = self.config.slippage_buffer_bps as f64 / 10_000.0;
+        let in_amount = quote.in_amount();
+        let out_amount = quote.out_amount();
+        let other_amount_threshold = quote.other_amount_threshold();
+
+        let profit = match quote.swap_mode() {
+            SwapMode::ExactIn => {
+                let worst_case_out = (out_amount * (1.0 - buffer)).min(other_amount_threshold).max(0.0);
+                worst_case_out - in_amount
+            }
+            SwapMode::ExactOut => {
+                let worst_case_in = (in_amount * (1.0 + buffer)).max(other_amount_threshold);
+                out_amount - worst_case_in
+            }
+        };
+
+        profit >= self.config.min_profit_threshold && profit >= opportunity.expected_profit * 0.8
+    }
+
     /// Analyze opportunity using Gemini AI (placeholder)
     pub async fn analyze_with_ai(&self, opportunity: &TradeOpportunity) -> Result<bool> {
         // Placeholder for Gemini AI Studio integration
@@ -208,3 +641,178 @@ impl Sniper {
         Ok(opportunity.expected_profit > self.config.min_profit_threshold)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ExecutionMode;
+    use crate::metrics::Metrics;
+    use solana_sdk::message::Message;
+    use solana_sdk::signature::Signature;
+
+    fn test_config() -> Config {
+        Config {
+            execution_mode: ExecutionMode::Live,
+            ..Default::default()
+        }
+    }
+
+    fn test_opportunity(swap_mode: SwapMode) -> TradeOpportunity {
+        TradeOpportunity {
+            pool_address: "pool1".to_string(),
+            token_in: "SOL".to_string(),
+            token_out: "USDC".to_string(),
+            amount_in: 1.0,
+            expected_amount_out: 1.05,
+            expected_profit: 0.02,
+            timestamp: 0,
+            slot: 1,
+            swap_mode,
+        }
+    }
+
+    fn test_sniper(config: Arc<Config>) -> Sniper {
+        let pools = Arc::new(RwLock::new(Vec::new()));
+        let metrics = Arc::new(Metrics::new());
+        let bank = Arc::new(Mutex::new(SimulationBank::new()));
+        Sniper::new_simulated(config, pools, metrics, bank)
+    }
+
+    fn jupiter_quote(in_amount: f64, out_amount: f64, other_amount_threshold: f64, swap_mode: SwapMode) -> Quote {
+        Quote::Jupiter(JupiterQuote {
+            input_mint: "SOL".to_string(),
+            output_mint: "USDC".to_string(),
+            in_amount: ((in_amount * 1e9) as u64).to_string(),
+            out_amount: ((out_amount * 1e9) as u64).to_string(),
+            other_amount_threshold: ((other_amount_threshold * 1e9) as u64).to_string(),
+            swap_mode: swap_mode.as_jupiter_str().to_string(),
+            slippage_bps: 50,
+            price_impact_pct: "0.1".to_string(),
+            route_plan: Vec::new(),
+        })
+    }
+
+    #[tokio::test]
+    async fn verify_profitability_exact_in_uses_other_amount_threshold_when_tighter() {
+        let sniper = test_sniper(Arc::new(test_config()));
+        let opportunity = test_opportunity(SwapMode::ExactIn);
+
+        // 1% slippage buffer alone would allow 1.05 * 0.99 = 1.0395, but the quote's own
+        // worst-case guarantee (1.03) is tighter and must win: profit = 1.03 - 1.0 = 0.03.
+        let quote = jupiter_quote(1.0, 1.05, 1.03, SwapMode::ExactIn);
+        assert!(sniper.verify_profitability(&quote, &opportunity));
+    }
+
+    #[tokio::test]
+    async fn verify_profitability_exact_in_rejects_when_worst_case_erases_profit() {
+        let sniper = test_sniper(Arc::new(test_config()));
+        let opportunity = test_opportunity(SwapMode::ExactIn);
+
+        // other_amount_threshold of 1.0 equals the input amount: worst-case profit is zero.
+        let quote = jupiter_quote(1.0, 1.05, 1.0, SwapMode::ExactIn);
+        assert!(!sniper.verify_profitability(&quote, &opportunity));
+    }
+
+    #[tokio::test]
+    async fn verify_profitability_exact_out_uses_worse_of_buffer_and_threshold() {
+        let sniper = test_sniper(Arc::new(test_config()));
+        let opportunity = test_opportunity(SwapMode::ExactOut);
+
+        // 1% buffer alone would allow paying up to 1.01, but the threshold (1.02) is the
+        // stricter (larger, i.e. worse) worst-case input and must win: profit = 1.05 - 1.02.
+        let quote = jupiter_quote(1.0, 1.05, 1.02, SwapMode::ExactOut);
+        assert!(sniper.verify_profitability(&quote, &opportunity));
+    }
+
+    #[test]
+    fn decode_versioned_transaction_round_trips() {
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::default()),
+        };
+        let raw = bincode::serialize(&tx).expect("serialize");
+        let encoded = BASE64.encode(raw);
+
+        let decoded = decode_versioned_transaction(&encoded).expect("decode");
+        assert_eq!(decoded.signatures, tx.signatures);
+        assert!(matches!(decoded.message, VersionedMessage::Legacy(_)));
+    }
+
+    #[test]
+    fn jupiter_quote_deserializes_from_real_camel_case_payload() {
+        let payload = serde_json::json!({
+            "inputMint": "So11111111111111111111111111111111111111112",
+            "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "inAmount": "1000000000",
+            "outAmount": "105000000",
+            "otherAmountThreshold": "103000000",
+            "swapMode": "ExactIn",
+            "slippageBps": 50,
+            "priceImpactPct": "0.1",
+            "routePlan": [
+                {
+                    "swapInfo": {
+                        "ammKey": "amm1",
+                        "label": "Orca",
+                        "inputMint": "So11111111111111111111111111111111111111112",
+                        "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                        "inAmount": "1000000000",
+                        "outAmount": "105000000",
+                        "feeAmount": "1000",
+                        "feeMint": "So11111111111111111111111111111111111111112"
+                    },
+                    "percent": 100
+                }
+            ]
+        });
+
+        let quote: JupiterQuote = serde_json::from_value(payload).expect("real Jupiter v6 payload must deserialize");
+        assert_eq!(quote.in_amount, "1000000000");
+        assert_eq!(quote.out_amount, "105000000");
+        assert_eq!(quote.route_plan.len(), 1);
+        assert_eq!(quote.route_plan[0].swap_info.amm_key, "amm1");
+    }
+
+    #[test]
+    fn jupiter_swap_request_serializes_to_camel_case_for_the_wire() {
+        let request = JupiterSwapRequest {
+            quote_response: JupiterQuote {
+                input_mint: "SOL".to_string(),
+                output_mint: "USDC".to_string(),
+                in_amount: "1000000000".to_string(),
+                out_amount: "1050000000".to_string(),
+                other_amount_threshold: "1030000000".to_string(),
+                swap_mode: "ExactIn".to_string(),
+                slippage_bps: 50,
+                price_impact_pct: "0.1".to_string(),
+                route_plan: Vec::new(),
+            },
+            user_public_key: "wallet1".to_string(),
+            wrap_unwrap_sol: true,
+        };
+
+        let value = serde_json::to_value(&request).expect("serialize");
+        assert!(value.get("quoteResponse").is_some());
+        assert!(value.get("userPublicKey").is_some());
+        assert_eq!(value.get("wrapAndUnwrapSol").and_then(|v| v.as_bool()), Some(true));
+    }
+
+    #[test]
+    fn jupiter_swap_response_deserializes_from_real_camel_case_payload() {
+        let payload = serde_json::json!({ "swapTransaction": "base64data" });
+        let response: JupiterSwapResponse = serde_json::from_value(payload).expect("real Jupiter v6 payload must deserialize");
+        assert_eq!(response.swap_transaction, "base64data");
+    }
+
+    #[tokio::test]
+    async fn resolve_address_lookup_tables_is_empty_for_legacy_messages_without_network() {
+        let config = test_config();
+        let client = reqwest::Client::new();
+        let message = VersionedMessage::Legacy(Message::default());
+
+        let tables = resolve_address_lookup_tables(&config, &client, &message)
+            .await
+            .expect("legacy messages resolve without any RPC call");
+        assert!(tables.is_empty());
+    }
+}
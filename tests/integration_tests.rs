@@ -1,4 +1,29 @@
-use sai_hf_bot::*;
+use sai_hf_bot::event_loop::{EventLoop, PoolData, TradeOpportunity};
+use sai_hf_bot::price_feed::{PriceSource, ReserveRatioSource};
+use sai_hf_bot::simulation::SimulationBank;
+use sai_hf_bot::{Config, ExecutionMode, Metrics, Sniper, SwapMode};
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+
+fn test_config() -> Config {
+    Config {
+        execution_mode: ExecutionMode::Mock,
+        ..Default::default()
+    }
+}
+
+fn test_pool() -> PoolData {
+    PoolData {
+        pool_address: "pool1".to_string(),
+        token_a: "So11111111111111111111111111111111111111112".to_string(),
+        token_b: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+        liquidity_a: 1_000.0,
+        liquidity_b: 1_020.0,
+        price: 1.02,
+        last_updated: 0,
+        slot: 42,
+    }
+}
 
 #[tokio::test]
 async fn test_config_validation() {
@@ -8,15 +33,68 @@ async fn test_config_validation() {
 
 #[tokio::test]
 async fn test_pool_monitoring() {
-    // This test would validate pool monitoring functionality
-    // Skipping actual test as it requires API mocking
+    let config = Arc::new(test_config());
+    let metrics = Arc::new(Metrics::new());
+    let event_loop = EventLoop::new(config, metrics);
+
+    let pool = test_pool();
+    event_loop.pools_handle().write().await.push(pool.clone());
+
+    let pools = event_loop.get_pools().await;
+    assert_eq!(pools.len(), 1);
+    assert_eq!(pools[0].pool_address, pool.pool_address);
+
+    // The reserve-ratio price source should deterministically reflect the seeded
+    // reserves, independent of any live Jupiter/Moralis call.
+    let price = ReserveRatioSource.price(&pool).await.unwrap();
+    assert_eq!(price, Some(pool.liquidity_b / pool.liquidity_a));
 }
 
 #[tokio::test]
 async fn test_trade_execution() {
-    // This test would validate trade execution logic
-    // Skipping actual test as it requires API mocking
-}
+    let config = Arc::new(test_config());
+    let pools = Arc::new(RwLock::new(Vec::new()));
+    let metrics = Arc::new(Metrics::new());
 
-// Integration tests would go here
-// These would test the full flow with mocked APIs
+    let pool = test_pool();
+    pools.write().await.push(pool.clone());
+
+    let mut bank = SimulationBank::new();
+    bank.seed_wallet(&config.wallet_private_key, 10.0);
+    bank.seed_pool(&pool);
+    let bank = Arc::new(Mutex::new(bank));
+
+    let sniper = Sniper::new_simulated(config.clone(), pools, metrics, bank.clone());
+
+    // expected_amount_out/expected_profit are chosen so the quote survives
+    // `verify_profitability`'s slippage buffer: worst-case out = 1.05 * 0.99 = 1.0395,
+    // profit = 0.0395, comfortably above both min_profit_threshold (0.01) and
+    // expected_profit * 0.8 (0.016).
+    let opportunity = TradeOpportunity {
+        pool_address: pool.pool_address.clone(),
+        token_in: pool.token_a.clone(),
+        token_out: pool.token_b.clone(),
+        amount_in: 1.0,
+        expected_amount_out: 1.05,
+        expected_profit: 0.02,
+        timestamp: 0,
+        slot: pool.slot,
+        swap_mode: SwapMode::ExactIn,
+    };
+
+    let result = sniper
+        .execute_trade(&opportunity)
+        .await
+        .expect("simulated trade execution should not error");
+
+    // The bank settles the winning quote's own amounts (from the Mock-mode placeholder
+    // quote, priced off expected_amount_out), not the opportunity's raw fields directly -
+    // this trade now runs through the same quote/verify_profitability/router-selection
+    // path the live path uses, with only the final submission swapped for the bank.
+    assert!(result.success, "trade should succeed: {:?}", result.error);
+    assert_eq!(result.amount_out, 1.05);
+    assert!((result.actual_profit - 0.05).abs() < 1e-9);
+
+    let wallet_balance = bank.lock().await.balance(&config.wallet_private_key);
+    assert!((wallet_balance - 10.05).abs() < 1e-9);
+}